@@ -1,4 +1,4 @@
-use crate::{Error, MessagePath, Result};
+use crate::{Duration, Error, MessagePath, MessageValue, Result, Time, Value};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -234,6 +234,112 @@ impl DataType {
         }
     }
 
+    /// Returns the path of the message, if the type is a message from any package.
+    ///
+    /// Messages from the same package only know their name, so they return `None`, just like
+    /// built in types. Their path is the name combined with the package of the containing message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, MessagePath};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert_eq!(
+    ///     DataType::parse("geometry_msgs/Position")?.message_path(),
+    ///     Some(&MessagePath::new("geometry_msgs", "Position")?),
+    /// );
+    /// assert_eq!(
+    ///     DataType::parse("Header")?.message_path(),
+    ///     Some(&MessagePath::new("std_msgs", "Header")?),
+    /// );
+    /// assert_eq!(DataType::parse("Position")?.message_path(), None);
+    /// assert_eq!(DataType::parse("int16")?.message_path(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn message_path(&self) -> Option<&MessagePath> {
+        match self {
+            DataType::GlobalMessage(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Returns the zero value of the data type.
+    ///
+    /// Numbers are zero, strings are empty, and times and durations are zero length.
+    /// Message types return an empty message, since their fields aren't known here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, MessageValue, Value};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert_eq!(DataType::parse("bool")?.default_value(), Value::Bool(false));
+    /// assert_eq!(DataType::parse("byte")?.default_value(), Value::I8(0));
+    /// assert_eq!(DataType::parse("float64")?.default_value(), Value::F64(0.0));
+    /// assert_eq!(DataType::parse("string")?.default_value(), Value::String("".into()));
+    /// assert_eq!(
+    ///     DataType::parse("geometry_msgs/Point")?.default_value(),
+    ///     Value::Message(MessageValue::new()),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_value(&self) -> Value {
+        match self {
+            DataType::Bool => Value::Bool(false),
+            DataType::I8(_) => Value::I8(0),
+            DataType::I16 => Value::I16(0),
+            DataType::I32 => Value::I32(0),
+            DataType::I64 => Value::I64(0),
+            DataType::U8(_) => Value::U8(0),
+            DataType::U16 => Value::U16(0),
+            DataType::U32 => Value::U32(0),
+            DataType::U64 => Value::U64(0),
+            DataType::F32 => Value::F32(0.0),
+            DataType::F64 => Value::F64(0.0),
+            DataType::String => Value::String(String::new()),
+            DataType::Time => Value::Time(Time::default()),
+            DataType::Duration => Value::Duration(Duration::default()),
+            DataType::LocalMessage(_) | DataType::GlobalMessage(_) => {
+                Value::Message(MessageValue::new())
+            }
+        }
+    }
+
+    /// Returns the number of bytes a value of the data type takes up when serialized.
+    ///
+    /// Strings and messages return `None`, since their size depends on the value. Whether the
+    /// field is an array is not part of the data type, so that is up to the caller to account for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::DataType;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert_eq!(DataType::parse("bool")?.byte_size(), Some(1));
+    /// assert_eq!(DataType::parse("int32")?.byte_size(), Some(4));
+    /// assert_eq!(DataType::parse("float64")?.byte_size(), Some(8));
+    /// assert_eq!(DataType::parse("time")?.byte_size(), Some(8));
+    /// assert_eq!(DataType::parse("string")?.byte_size(), None);
+    /// assert_eq!(DataType::parse("geometry_msgs/Point")?.byte_size(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn byte_size(&self) -> Option<usize> {
+        match self {
+            DataType::Bool | DataType::I8(_) | DataType::U8(_) => Some(1),
+            DataType::I16 | DataType::U16 => Some(2),
+            DataType::I32 | DataType::U32 | DataType::F32 => Some(4),
+            DataType::I64 | DataType::U64 | DataType::F64 => Some(8),
+            DataType::Time | DataType::Duration => Some(8),
+            DataType::String | DataType::LocalMessage(_) | DataType::GlobalMessage(_) => None,
+        }
+    }
+
     /// Returns the representation of the data type when constructing the MD5 sum.
     ///
     /// For built in types, it is the same as the data type name.
@@ -1,3 +1,5 @@
+use crate::Value;
+
 /// Enumeration of all errors that can be returned.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -21,6 +23,24 @@ pub enum Error {
         /// Reason for the failure.
         reason: String,
     },
+    /// Field in the `msg` or `srv` file has an attribute that is not supported.
+    ///
+    /// This is only returned when parsing in strict mode.
+    #[error("unsupported field attribute `{attribute}` in line `{line}`")]
+    UnsupportedFieldAttribute {
+        /// Name of the unsupported attribute.
+        attribute: String,
+        /// Line of the message that contains the attribute.
+        line: String,
+    },
+    /// A field can't be added to a message, because the message already has a field with that name.
+    #[error("message {message} already has a field named `{name}`")]
+    DuplicateField {
+        /// Name of the duplicate field.
+        name: String,
+        /// Path of the message that the field was added to.
+        message: String,
+    },
     /// The `msg` or `srv` file being parsed has invalid content.
     #[error("bad content in message: `{0}`")]
     BadMessageContent(String),
@@ -35,6 +55,14 @@ pub enum Error {
         /// Name of the missing message.
         name: String,
     },
+    /// Messages embed each other in a cycle, so their MD5 sums can't be calculated.
+    #[error("message dependency cycle through {package}/{name}")]
+    MessageDependencyCycle {
+        /// Package that the message is located in.
+        package: String,
+        /// Name of the message that depends on itself.
+        name: String,
+    },
     /// Passed in constant value is not parsable as its data type.
     #[error("bad constant value `{value}` of type {datatype} in field {name}")]
     BadConstant {
@@ -45,7 +73,48 @@ pub enum Error {
         /// The invalid value provided.
         value: String,
     },
+    /// An array operation was attempted on a value that is not an array.
+    #[error("expected an array value, found {found}")]
+    NotAnArray {
+        /// ROS type of the value that was provided.
+        found: &'static str,
+    },
+    /// An item can't be added to an array, because its type differs from the existing elements.
+    #[error("cannot add {found} value to an array of {expected} values")]
+    ArrayElementMismatch {
+        /// ROS type of the array's elements.
+        expected: &'static str,
+        /// ROS type of the item that was provided.
+        found: &'static str,
+    },
+    /// A JSON value doesn't fit the message definition it's being converted with.
+    #[error("JSON value at `{path}` doesn't fit the message definition, {reason}")]
+    InvalidJsonValue {
+        /// Path to the value, starting with the message type, e.g. `foo/Bar.baz[3]`.
+        path: String,
+        /// Reason for the failure.
+        reason: String,
+    },
 }
 
 /// Convenience type for shorter return value syntax of this crate's errors.
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error returned when a `Value` can't be converted into the requested type.
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+#[error("cannot convert {found} value into `{expected}`")]
+pub struct ConversionError {
+    /// Name of the type we tried to convert into.
+    pub expected: &'static str,
+    /// ROS type of the value that was provided.
+    pub found: &'static str,
+}
+
+impl ConversionError {
+    pub(crate) fn new<T>(found: &Value) -> Self {
+        Self {
+            expected: std::any::type_name::<T>(),
+            found: found.kind(),
+        }
+    }
+}
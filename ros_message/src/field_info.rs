@@ -101,6 +101,75 @@ impl FieldInfo {
         Self::evaluate(datatype.try_into()?, name.into(), case)
     }
 
+    /// Create a single item field of the provided type, named `field`.
+    ///
+    /// Combined with `with_name` and `with_case`, this allows building fields without
+    /// handling errors, which is convenient for creating test data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, FieldInfo, FieldCase};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let field = FieldInfo::from_datatype(DataType::I16)
+    ///     .with_name("foo")
+    ///     .with_case(FieldCase::Vector);
+    ///
+    /// assert_eq!(field, FieldInfo::new("int16", "foo", FieldCase::Vector)?);
+    /// assert_eq!(FieldInfo::from_datatype(DataType::F64).name(), "field");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_datatype(datatype: DataType) -> FieldInfo {
+        FieldInfo {
+            datatype,
+            name: "field".into(),
+            case: FieldCase::Unit,
+            const_value: Uncompared { inner: None },
+        }
+    }
+
+    /// Returns the field with its name replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, FieldInfo};
+    /// let field = FieldInfo::from_datatype(DataType::String).with_name("label");
+    ///
+    /// assert_eq!(field.name(), "label");
+    /// assert_eq!(format!("{}", field), "string label");
+    /// ```
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns the field with its variant replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is a constant whose value is invalid for the data type of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, FieldInfo, FieldCase, Value};
+    /// let field = FieldInfo::from_datatype(DataType::U32)
+    ///     .with_name("FOO")
+    ///     .with_case(FieldCase::Const("12".into()));
+    ///
+    /// assert!(field.is_constant());
+    /// assert_eq!(field.const_value(), Some(&Value::U32(12)));
+    /// ```
+    pub fn with_case(self, case: FieldCase) -> Self {
+        match Self::evaluate(self.datatype, self.name, case) {
+            Ok(field) => field,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
     fn evaluate(datatype: DataType, name: String, case: FieldCase) -> Result<FieldInfo> {
         let const_value = match &case {
             FieldCase::Const(raw_value) => Some(
@@ -175,6 +244,48 @@ impl FieldInfo {
         matches!(self.case, FieldCase::Const(..))
     }
 
+    /// Returns the value the field holds in a default constructed message.
+    ///
+    /// Vectors are empty, fixed size arrays are filled with the data type's default value,
+    /// and constants return their stored value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{DataType, FieldInfo, FieldCase, Value};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert_eq!(
+    ///     FieldInfo::new("int16", "foo", FieldCase::Unit)?.default_value(),
+    ///     Value::I16(0),
+    /// );
+    /// assert_eq!(
+    ///     FieldInfo::new("int16", "foo", FieldCase::Vector)?.default_value(),
+    ///     Value::Array(vec![]),
+    /// );
+    /// assert_eq!(
+    ///     FieldInfo::new("int16", "foo", FieldCase::Array(2))?.default_value(),
+    ///     Value::Array(vec![Value::I16(0), Value::I16(0)]),
+    /// );
+    /// assert_eq!(
+    ///     FieldInfo::new("int16", "foo", FieldCase::Const("12".into()))?.default_value(),
+    ///     Value::I16(12),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_value(&self) -> Value {
+        match &self.case {
+            FieldCase::Unit => self.datatype.default_value(),
+            FieldCase::Vector => Value::Array(vec![]),
+            FieldCase::Array(len) => Value::Array(vec![self.datatype.default_value(); *len]),
+            FieldCase::Const(_) => self
+                .const_value()
+                .cloned()
+                .unwrap_or_else(|| self.datatype.default_value()),
+        }
+    }
+
     /// Returns the representation of the data type when constructing the MD5 sum.
     ///
     /// For built in types, it is the same as the message row, but with consistent whitespace.
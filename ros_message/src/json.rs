@@ -0,0 +1,268 @@
+use crate::{DataType, Error, FieldCase, MessagePath, MessageValue, Msg, Result, Value};
+use serde_json::{Map, Number, Value as Json};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Converts a message into JSON.
+///
+/// Messages become objects, arrays become arrays, and primitives become numbers, strings and
+/// booleans. Times and durations become objects with `sec` and `nsec` fields.
+///
+/// Floats that JSON can't represent, like NaN and infinities, become `null`. `from_json` reads
+/// `null` back as NaN, so infinities don't survive the round trip.
+///
+/// # Examples
+///
+/// ```
+/// # use ros_message::{to_json, MessageValue, Value};
+/// let mut message = MessageValue::new();
+/// message.insert("id".into(), Value::U32(7));
+/// message.insert("tags".into(), Value::from(vec!["a".to_owned(), "b".to_owned()]));
+///
+/// assert_eq!(
+///     to_json(&message),
+///     serde_json::json!({"id": 7, "tags": ["a", "b"]}),
+/// );
+/// ```
+pub fn to_json(message: &MessageValue) -> Json {
+    Json::Object(
+        message
+            .iter()
+            .map(|(name, value)| (name.clone(), value_to_json(value)))
+            .collect(),
+    )
+}
+
+fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Bool(v) => Json::Bool(*v),
+        Value::I8(v) => Json::from(*v),
+        Value::I16(v) => Json::from(*v),
+        Value::I32(v) => Json::from(*v),
+        Value::I64(v) => Json::from(*v),
+        Value::U8(v) => Json::from(*v),
+        Value::U16(v) => Json::from(*v),
+        Value::U32(v) => Json::from(*v),
+        Value::U64(v) => Json::from(*v),
+        Value::F32(v) => float_to_json(f64::from(*v)),
+        Value::F64(v) => float_to_json(*v),
+        Value::String(v) => Json::String(v.clone()),
+        Value::Time(v) => json_object(i64::from(v.sec), i64::from(v.nsec)),
+        Value::Duration(v) => json_object(i64::from(v.sec), i64::from(v.nsec)),
+        Value::Array(items) => Json::Array(items.iter().map(value_to_json).collect()),
+        Value::Message(message) => to_json(message),
+    }
+}
+
+fn float_to_json(value: f64) -> Json {
+    Number::from_f64(value).map_or(Json::Null, Json::Number)
+}
+
+fn json_object(sec: i64, nsec: i64) -> Json {
+    let mut object = Map::new();
+    object.insert("sec".into(), sec.into());
+    object.insert("nsec".into(), nsec.into());
+    Json::Object(object)
+}
+
+/// Converts JSON into a message, following the provided message definition.
+///
+/// This is the inverse of `to_json`, except that `null` floats become NaN, whether they were
+/// NaN or infinite to begin with. Fields missing from the JSON object are filled in with
+/// their default values, while fields that aren't in the definition are rejected. Constants are
+/// not included in the message.
+///
+/// The definition of every message embedded in it, even indirectly, must be provided in the
+/// passed in messages.
+///
+/// # Errors
+///
+/// An error is returned if the JSON doesn't fit the definition, or if the definition of some
+/// embedded message is missing.
+///
+/// # Examples
+///
+/// ```
+/// # use ros_message::{from_json, Msg, Value};
+/// # use std::collections::HashMap;
+/// # use std::convert::TryInto;
+/// #
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let msg = Msg::new("foo/Bar".try_into()?, "uint32 id\nstring[] tags")?;
+///
+/// let message = from_json(serde_json::json!({"id": 7}), &msg, &HashMap::new())?;
+/// assert_eq!(message["id"], Value::U32(7));
+/// assert_eq!(message["tags"], Value::Array(vec![]));
+///
+/// let error = from_json(serde_json::json!({"id": -7}), &msg, &HashMap::new()).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "JSON value at `foo/Bar.id` doesn't fit the message definition, expected uint32, found -7",
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_json(
+    json: Json,
+    msg: &Msg,
+    messages: &HashMap<MessagePath, Msg>,
+) -> Result<MessageValue> {
+    message_from_json(json, msg, messages, &msg.path().to_string())
+}
+
+fn message_from_json(
+    json: Json,
+    msg: &Msg,
+    messages: &HashMap<MessagePath, Msg>,
+    path: &str,
+) -> Result<MessageValue> {
+    let mut object = match json {
+        Json::Object(object) => object,
+        other => return Err(mismatch(path, &format!("message {}", msg.path()), &other)),
+    };
+
+    let mut message = MessageValue::new();
+    for field in msg.fields().iter().filter(|field| !field.is_constant()) {
+        let field_path = format!("{}.{}", path, field.name());
+        let value = match object.remove(field.name()) {
+            Some(json) => field_from_json(
+                json,
+                field.datatype(),
+                field.case(),
+                msg,
+                messages,
+                &field_path,
+            )?,
+            None if field.datatype().is_builtin() => field.default_value(),
+            // Embedded messages are filled in from their own definitions.
+            None => field_from_json(
+                default_message_json(field.case()),
+                field.datatype(),
+                field.case(),
+                msg,
+                messages,
+                &field_path,
+            )?,
+        };
+        message.insert(field.name().into(), value);
+    }
+
+    if let Some(name) = object.keys().next() {
+        return Err(Error::InvalidJsonValue {
+            path: format!("{}.{}", path, name),
+            reason: format!("message {} has no such field", msg.path()),
+        });
+    }
+
+    Ok(message)
+}
+
+fn default_message_json(case: &FieldCase) -> Json {
+    match case {
+        FieldCase::Vector => Json::Array(vec![]),
+        FieldCase::Array(length) => Json::Array(vec![Json::Object(Map::new()); *length]),
+        FieldCase::Unit | FieldCase::Const(_) => Json::Object(Map::new()),
+    }
+}
+
+fn field_from_json(
+    json: Json,
+    datatype: &DataType,
+    case: &FieldCase,
+    msg: &Msg,
+    messages: &HashMap<MessagePath, Msg>,
+    path: &str,
+) -> Result<Value> {
+    let items = match (case, json) {
+        (FieldCase::Unit, json) | (FieldCase::Const(_), json) => {
+            return value_from_json(json, datatype, msg, messages, path)
+        }
+        (FieldCase::Vector, Json::Array(items)) => items,
+        (FieldCase::Array(length), Json::Array(items)) if items.len() == *length => items,
+        (FieldCase::Array(length), Json::Array(items)) => {
+            return Err(Error::InvalidJsonValue {
+                path: path.into(),
+                reason: format!("expected {} items, found {}", length, items.len()),
+            })
+        }
+        (_, other) => return Err(mismatch(path, &format!("{}[]", datatype), &other)),
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let item_path = format!("{}[{}]", path, index);
+            value_from_json(item, datatype, msg, messages, &item_path)
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Value::Array)
+}
+
+fn value_from_json(
+    json: Json,
+    datatype: &DataType,
+    msg: &Msg,
+    messages: &HashMap<MessagePath, Msg>,
+    path: &str,
+) -> Result<Value> {
+    let expected = datatype.to_string();
+    let value = match datatype {
+        DataType::Bool => json.as_bool().map(Value::Bool),
+        DataType::I8(_) => integer(&json).map(Value::I8),
+        DataType::I16 => integer(&json).map(Value::I16),
+        DataType::I32 => integer(&json).map(Value::I32),
+        DataType::I64 => json.as_i64().map(Value::I64),
+        DataType::U8(_) => integer(&json).map(Value::U8),
+        DataType::U16 => integer(&json).map(Value::U16),
+        DataType::U32 => integer(&json).map(Value::U32),
+        DataType::U64 => json.as_u64().map(Value::U64),
+        DataType::F32 => float(&json).map(|v| Value::F32(v as f32)),
+        DataType::F64 => float(&json).map(Value::F64),
+        DataType::String => json.as_str().map(|v| Value::String(v.into())),
+        DataType::Time => serde_json::from_value(json.clone()).ok().map(Value::Time),
+        DataType::Duration => serde_json::from_value(json.clone())
+            .ok()
+            .map(Value::Duration),
+        DataType::LocalMessage(name) => {
+            let dependency = embedded_message(&msg.path().peer(name), messages)?;
+            return message_from_json(json, dependency, messages, path).map(Value::Message);
+        }
+        DataType::GlobalMessage(dependency) => {
+            let dependency = embedded_message(dependency, messages)?;
+            return message_from_json(json, dependency, messages, path).map(Value::Message);
+        }
+    };
+    value.ok_or_else(|| mismatch(path, &expected, &json))
+}
+
+// `to_json` writes floats that JSON can't represent as `null`.
+fn float(json: &Json) -> Option<f64> {
+    match json {
+        Json::Null => Some(f64::NAN),
+        json => json.as_f64(),
+    }
+}
+
+fn integer<T: TryFrom<i64>>(json: &Json) -> Option<T> {
+    T::try_from(json.as_i64()?).ok()
+}
+
+fn embedded_message<'a>(
+    path: &MessagePath,
+    messages: &'a HashMap<MessagePath, Msg>,
+) -> Result<&'a Msg> {
+    messages
+        .get(path)
+        .ok_or_else(|| Error::MessageDependencyMissing {
+            package: path.package().into(),
+            name: path.name().into(),
+        })
+}
+
+fn mismatch(path: &str, expected: &str, found: &Json) -> Error {
+    Error::InvalidJsonValue {
+        path: path.into(),
+        reason: format!("expected {}, found {}", expected, found),
+    }
+}
@@ -4,6 +4,8 @@
 mod data_type;
 mod error;
 mod field_info;
+#[cfg(feature = "json")]
+mod json;
 mod message_path;
 mod msg;
 mod parse_msg;
@@ -14,10 +16,13 @@ mod time;
 mod value;
 
 pub use data_type::{DataType, I8Variant, U8Variant};
-pub use error::{Error, Result};
+pub use error::{ConversionError, Error, Result};
 pub use field_info::{FieldCase, FieldInfo};
+#[cfg(feature = "json")]
+pub use json::{from_json, to_json};
 pub use message_path::MessagePath;
-pub use msg::Msg;
+pub use msg::{Msg, SchemaDiff};
+pub use parse_msg::ParseOptions;
 pub use srv::Srv;
 pub use time::{Duration, Time};
-pub use value::{MessageValue, Value};
+pub use value::{MessageValue, MessageValueExt, Value, ValuePattern};
@@ -105,6 +105,70 @@ impl MessagePath {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Checks whether the full path matches a shell style glob pattern.
+    ///
+    /// `*` matches any number of characters and `?` matches a single character, but neither
+    /// matches the `/` separating the package from the name. Matching is case sensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::MessagePath;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_path = MessagePath::new("std_msgs", "Header")?;
+    ///
+    /// assert!(message_path.matches_glob("std_msgs/*"));
+    /// assert!(message_path.matches_glob("*/Header"));
+    /// assert!(message_path.matches_glob("*/*"));
+    /// assert!(message_path.matches_glob("std_*/Head?r"));
+    /// assert!(!message_path.matches_glob("*"));
+    /// assert!(!message_path.matches_glob("*/header"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matches_glob(&self, pattern: &str) -> bool {
+        let path = self.to_string().chars().collect::<Vec<char>>();
+        let pattern = pattern.chars().collect::<Vec<char>>();
+        glob_match(&pattern, &path)
+    }
+
+    /// Checks whether the message is located in the given package.
+    ///
+    /// Unlike `matches_glob`, the package is compared as is. Since package names can't be empty,
+    /// an empty package never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::MessagePath;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_path = MessagePath::new("std_msgs", "Header")?;
+    ///
+    /// assert!(message_path.matches_prefix("std_msgs"));
+    /// assert!(!message_path.matches_prefix("std"));
+    /// assert!(!message_path.matches_prefix(""));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matches_prefix(&self, package: &str) -> bool {
+        self.package == package
+    }
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match (pattern.first(), path.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            glob_match(&pattern[1..], path)
+                || (matches!(path.first(), Some(&c) if c != '/') && glob_match(pattern, &path[1..]))
+        }
+        (Some('?'), Some(&c)) => c != '/' && glob_match(&pattern[1..], &path[1..]),
+        (Some(p), Some(c)) => p == c && glob_match(&pattern[1..], &path[1..]),
+        _ => false,
+    }
 }
 
 impl Display for MessagePath {
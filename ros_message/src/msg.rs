@@ -1,6 +1,7 @@
-use crate::{parse_msg::match_lines, DataType, Error, FieldInfo, MessagePath, Result, Value};
+use crate::parse_msg::{match_lines_with_options, normalize_source};
+use crate::{DataType, Error, FieldCase, FieldInfo, MessagePath, ParseOptions, Result, Value};
 use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Formatter;
@@ -53,8 +54,51 @@ impl Msg {
     /// # }
     /// ```
     pub fn new(path: MessagePath, source: &str) -> Result<Msg> {
-        let source = source.trim().to_owned();
-        let fields = match_lines(&source)?;
+        Self::from_str_with_options(path, source, ParseOptions::default())
+    }
+
+    /// Create a message from a passed in path and source, with custom parsing options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is an error parsing the message source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{Msg, ParseOptions};
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let source = r#"
+    ///     float64 x [unit="meters"]
+    ///     float64 y
+    ///     "#;
+    ///
+    /// let lenient = Msg::from_str_with_options(
+    ///     "foo/Bar".try_into()?,
+    ///     source,
+    ///     ParseOptions { strict: false },
+    /// )?;
+    /// assert_eq!(lenient.fields().len(), 2);
+    /// assert_eq!(lenient.fields()[0].name(), "x");
+    ///
+    /// assert!(Msg::from_str_with_options(
+    ///     "foo/Bar".try_into()?,
+    ///     source,
+    ///     ParseOptions { strict: true },
+    /// )
+    /// .is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_with_options(
+        path: MessagePath,
+        source: &str,
+        options: ParseOptions,
+    ) -> Result<Msg> {
+        let source = normalize_source(source).trim().to_owned();
+        let fields = match_lines_with_options(&source, options)?;
         Ok(Msg {
             path,
             fields,
@@ -62,6 +106,57 @@ impl Msg {
         })
     }
 
+    /// Create a message from a passed in path and source, skipping over invalid lines.
+    ///
+    /// Instead of failing at the first malformed line, every line that can't be parsed is
+    /// dropped and its error collected, so all problems in the source are reported at once.
+    ///
+    /// The source of the returned message only contains the lines that were parsed successfully.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Msg;
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (message, errors) = Msg::from_str_recovering(
+    ///     "foo/Bar".try_into()?,
+    ///     r#"uint32 a
+    ///     uint32 ???
+    ///     float64 b
+    ///     byte[x] c
+    ///     "#,
+    /// );
+    ///
+    /// assert_eq!(message.fields().len(), 2);
+    /// assert_eq!(message.source(), "uint32 a\n    float64 b");
+    /// assert_eq!(errors.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_str_recovering(path: MessagePath, source: &str) -> (Msg, Vec<Error>) {
+        let mut fields = vec![];
+        let mut errors = vec![];
+        let mut lines = vec![];
+        let source = normalize_source(source);
+        for line in source.trim().split('\n') {
+            match match_lines_with_options(line, ParseOptions::default()) {
+                Ok(line_fields) => {
+                    fields.extend(line_fields);
+                    lines.push(line);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        let message = Msg {
+            path,
+            fields,
+            source: lines.join("\n").trim().to_owned(),
+        };
+        (message, errors)
+    }
+
     /// Returns a map of all constant fields inside the message, with their values parsed.
     ///
     /// # Examples
@@ -104,6 +199,218 @@ impl Msg {
             .collect()
     }
 
+    /// Returns a copy of the message with all constant fields removed.
+    ///
+    /// The source of the returned message is regenerated from the remaining fields,
+    /// so comments and formatting of the original source are not preserved.
+    ///
+    /// Constants are part of the MD5 representation, so the MD5 sum of the returned message
+    /// will differ from the original if any constants were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Msg;
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let message = Msg::new(
+    ///     "foo/Bar".try_into()?,
+    ///     r#"# a comment that is ignored
+    ///     uint32 a
+    ///     uint32 FOO=5
+    ///     string SOME_TEXT=this is # some text, don't be fooled by the hash
+    ///     byte[16] b
+    ///     "#,
+    /// )?;
+    ///
+    /// let stripped = message.strip_constants();
+    ///
+    /// assert_eq!(stripped.path(), message.path());
+    /// assert_eq!(stripped.fields().len(), 2);
+    /// assert!(stripped.constants().is_empty());
+    /// assert_eq!(stripped.source(), "uint32 a\nbyte[16] b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn strip_constants(&self) -> Msg {
+        let fields = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_constant())
+            .cloned()
+            .collect::<Vec<_>>();
+        let source = source_from_fields(&fields);
+        Msg {
+            path: self.path.clone(),
+            fields,
+            source,
+        }
+    }
+
+    /// Appends a field to the end of the message.
+    ///
+    /// The source of the message is regenerated from its fields, so comments are not kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message already has a field with the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{FieldCase, FieldInfo, Msg};
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut message = Msg::new("foo/Bar".try_into()?, "uint32 a # the first field")?;
+    ///
+    /// message.add_field(FieldInfo::new("float64", "b", FieldCase::Vector)?)?;
+    /// assert!(message.add_field(FieldInfo::new("string", "a", FieldCase::Unit)?).is_err());
+    ///
+    /// assert_eq!(message.fields().len(), 2);
+    /// assert_eq!(message.source(), "uint32 a\nfloat64[] b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_field(&mut self, field: FieldInfo) -> Result<()> {
+        if self.fields.iter().any(|f| f.name() == field.name()) {
+            return Err(Error::DuplicateField {
+                name: field.name().into(),
+                message: self.path.to_string(),
+            });
+        }
+        self.fields.push(field);
+        self.source = source_from_fields(&self.fields);
+        Ok(())
+    }
+
+    /// Removes the field with the given name, returning it if it was present.
+    ///
+    /// The source of the message is regenerated from its fields, so comments are not kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Msg;
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut message = Msg::new(
+    ///     "foo/Bar".try_into()?,
+    ///     r#"uint32 a
+    ///     uint32 FOO=5
+    ///     byte[16] b"#,
+    /// )?;
+    ///
+    /// assert_eq!(message.remove_field("FOO").unwrap().name(), "FOO");
+    /// assert!(message.remove_field("FOO").is_none());
+    ///
+    /// assert_eq!(message.source(), "uint32 a\nbyte[16] b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_field(&mut self, name: &str) -> Option<FieldInfo> {
+        let index = self.fields.iter().position(|field| field.name() == name)?;
+        let field = self.fields.remove(index);
+        self.source = source_from_fields(&self.fields);
+        Some(field)
+    }
+
+    /// Generates the C++ declaration of the message, compatible with `roscpp` serialization.
+    ///
+    /// The output contains the `struct` declaration inside the namespace of the package, followed
+    /// by the `ros::serialization::Serializer` specialization for it.
+    /// Variable length arrays map to `std::vector`, and fixed length arrays to `boost::array`.
+    /// Constants are declared as `static constexpr` members.
+    ///
+    /// Includes for the used headers and the dependencies of the message are not generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Msg;
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let message = Msg::new(
+    ///     "foo/Bar".try_into()?,
+    ///     r#"Header header
+    ///     uint8 FOO=5
+    ///     float64[] values
+    ///     Baz[3] bazzes"#,
+    /// )?;
+    ///
+    /// let declaration = message.to_cpp_declaration();
+    ///
+    /// assert!(declaration.contains("struct Bar {"));
+    /// assert!(declaration.contains("  static constexpr uint8_t FOO = 5;"));
+    /// assert!(declaration.contains("  std::vector<double> values;"));
+    /// assert!(declaration.contains("  boost::array<foo::Baz, 3> bazzes;"));
+    /// assert!(declaration.contains("struct Serializer<foo::Bar> {"));
+    /// assert!(declaration.contains("    stream.next(m.header);"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_cpp_declaration(&self) -> String {
+        let package = self.path.package();
+        let name = self.path.name();
+
+        let mut members = String::new();
+        let mut serialized = String::new();
+        for field in &self.fields {
+            let datatype = cpp_type(field.datatype(), package);
+            let member = match (field.case(), field.const_value()) {
+                (_, Some(Value::String(value))) => format!(
+                    "static constexpr const char* {} = \"{}\"",
+                    field.name(),
+                    value.replace('\\', "\\\\").replace('"', "\\\""),
+                ),
+                (_, Some(value)) => {
+                    format!("static constexpr {} {} = {}", datatype, field.name(), value)
+                }
+                (FieldCase::Vector, _) => format!("std::vector<{}> {}", datatype, field.name()),
+                (FieldCase::Array(length), _) => {
+                    format!("boost::array<{}, {}> {}", datatype, length, field.name())
+                }
+                _ => format!("{} {}", datatype, field.name()),
+            };
+            members += &format!("  {};\n", member);
+            if !field.is_constant() {
+                serialized += &format!("    stream.next(m.{});\n", field.name());
+            }
+        }
+
+        format!(
+            r#"namespace {package} {{
+
+struct {name} {{
+{members}}};
+
+}}  // namespace {package}
+
+namespace ros {{
+namespace serialization {{
+
+template <>
+struct Serializer<{package}::{name}> {{
+  template <typename Stream, typename T>
+  inline static void allInOne(Stream& stream, T m) {{
+{serialized}  }}
+
+  ROS_DECLARE_ALLINONE_SERIALIZER
+}};
+
+}}  // namespace serialization
+}}  // namespace ros
+"#,
+            package = package,
+            name = name,
+            members = members,
+            serialized = serialized,
+        )
+    }
+
     /// Returns the path of the message.
     pub fn path(&self) -> &MessagePath {
         &self.path
@@ -176,7 +483,6 @@ impl Msg {
     /// # Errors
     ///
     /// An error is returned if some dependency is missing in the hashes.
-    #[cfg(test)]
     pub fn calculate_md5(&self, hashes: &HashMap<MessagePath, String>) -> Result<String> {
         use md5::{Digest, Md5};
 
@@ -185,6 +491,78 @@ impl Msg {
         Ok(hex::encode(hasher.finalize()))
     }
 
+    /// Returns the MD5 sum of this message, computing the sums of its dependencies as needed.
+    ///
+    /// This is the sum that ROS compares to check that publishers and subscribers agree on
+    /// the message type. Following the ROS specification, constants come first, and every
+    /// embedded message is represented by its own MD5 sum, rather than its definition.
+    ///
+    /// The definition of every message embedded in it, even indirectly, must be provided in the
+    /// passed in messages.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the definition of some dependency is missing, or if the
+    /// messages depend on each other in a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Msg;
+    /// # use std::collections::HashMap;
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let point = Msg::new(
+    ///     "geometry_msgs/Point".try_into()?,
+    ///     "float64 x\nfloat64 y\nfloat64 z",
+    /// )?;
+    /// let points = Msg::new("foo/Points".try_into()?, "geometry_msgs/Point[] points")?;
+    ///
+    /// assert_eq!(point.md5sum(&HashMap::new())?, "4a842b65f413084dc2b10fb484ea7f17");
+    /// assert!(points.md5sum(&HashMap::new()).is_err());
+    ///
+    /// let mut messages = HashMap::new();
+    /// messages.insert(point.path().clone(), point);
+    /// assert_eq!(points.md5sum(&messages)?, "3fb3f9dacc279b964c4c8341122c34df");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn md5sum(&self, messages: &HashMap<MessagePath, Msg>) -> Result<String> {
+        let mut hashes = HashMap::new();
+        self.md5sum_with_hashes(messages, &mut hashes, &mut HashSet::new())
+    }
+
+    fn md5sum_with_hashes(
+        &self,
+        messages: &HashMap<MessagePath, Msg>,
+        hashes: &mut HashMap<MessagePath, String>,
+        in_progress: &mut HashSet<MessagePath>,
+    ) -> Result<String> {
+        in_progress.insert(self.path.clone());
+        for dependency in self.dependencies() {
+            if hashes.contains_key(&dependency) {
+                continue;
+            }
+            if in_progress.contains(&dependency) {
+                return Err(Error::MessageDependencyCycle {
+                    package: dependency.package().into(),
+                    name: dependency.name().into(),
+                });
+            }
+            let msg = messages
+                .get(&dependency)
+                .ok_or_else(|| Error::MessageDependencyMissing {
+                    package: dependency.package().into(),
+                    name: dependency.name().into(),
+                })?;
+            let hash = msg.md5sum_with_hashes(messages, hashes, in_progress)?;
+            hashes.insert(dependency, hash);
+        }
+        in_progress.remove(&self.path);
+        self.calculate_md5(hashes)
+    }
+
     /// Returns the full MD5 representation of the message.
     ///
     /// This is the string that is sent to the MD5 hasher to digest.
@@ -262,6 +640,170 @@ impl Msg {
     pub fn has_header(&self) -> bool {
         self.fields.iter().any(FieldInfo::is_header)
     }
+
+    /// Returns all fields that are present in both messages, with the same name and type.
+    ///
+    /// The fields are returned in the order they appear in this message.
+    /// Constants are only considered common if they also have the same value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{FieldCase, FieldInfo, Msg};
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let image = Msg::new(
+    ///     "sensor_msgs/Image".try_into()?,
+    ///     r#"
+    ///     Header header
+    ///     uint32 height
+    ///     uint32 width
+    ///     string encoding
+    ///     uint8[] data
+    ///     "#,
+    /// )?;
+    /// let compressed = Msg::new(
+    ///     "sensor_msgs/CompressedImage".try_into()?,
+    ///     r#"
+    ///     Header header
+    ///     string format
+    ///     uint8[] data
+    ///     "#,
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     image.common_fields_with(&compressed),
+    ///     vec![
+    ///         &FieldInfo::new("Header", "header", FieldCase::Unit)?,
+    ///         &FieldInfo::new("uint8", "data", FieldCase::Vector)?,
+    ///     ],
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn common_fields_with<'a>(&'a self, other: &'a Msg) -> Vec<&'a FieldInfo> {
+        self.fields
+            .iter()
+            .filter(|field| other.fields.contains(field))
+            .collect()
+    }
+
+    /// Compares the fields of this message with the fields of a newer version of it.
+    ///
+    /// Fields are matched by name. A matched field is considered changed if its type or
+    /// case differs, so changing the value of a constant also counts as a change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{FieldCase, FieldInfo, Msg};
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let old = Msg::new(
+    ///     "foo/Status".try_into()?,
+    ///     r#"
+    ///     Header header
+    ///     uint32 code
+    ///     string message
+    ///     "#,
+    /// )?;
+    /// let new = Msg::new(
+    ///     "foo/Status".try_into()?,
+    ///     r#"
+    ///     Header header
+    ///     uint64 code
+    ///     float64 progress
+    ///     "#,
+    /// )?;
+    ///
+    /// let diff = old.diff_schemas(&new);
+    /// assert_eq!(diff.added, vec![&FieldInfo::new("float64", "progress", FieldCase::Unit)?]);
+    /// assert_eq!(diff.removed, vec![&FieldInfo::new("string", "message", FieldCase::Unit)?]);
+    /// assert_eq!(
+    ///     diff.changed,
+    ///     vec![(
+    ///         &FieldInfo::new("uint32", "code", FieldCase::Unit)?,
+    ///         &FieldInfo::new("uint64", "code", FieldCase::Unit)?,
+    ///     )],
+    /// );
+    /// assert!(old.diff_schemas(&old).is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff_schemas<'a>(&'a self, other: &'a Msg) -> SchemaDiff<'a> {
+        let find =
+            |fields: &'a [FieldInfo], name: &str| fields.iter().find(|field| field.name() == name);
+
+        let mut diff = SchemaDiff::default();
+        for field in &self.fields {
+            match find(&other.fields, field.name()) {
+                Some(other_field) if other_field != field => {
+                    diff.changed.push((field, other_field))
+                }
+                Some(_) => {}
+                None => diff.removed.push(field),
+            }
+        }
+        diff.added = other
+            .fields
+            .iter()
+            .filter(|field| find(&self.fields, field.name()).is_none())
+            .collect();
+        diff
+    }
+}
+
+/// Differences between the fields of two versions of a message.
+///
+/// Returned by `Msg::diff_schemas`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaDiff<'a> {
+    /// Fields only present in the newer message, in the order they appear in it.
+    pub added: Vec<&'a FieldInfo>,
+    /// Fields only present in the older message, in the order they appear in it.
+    pub removed: Vec<&'a FieldInfo>,
+    /// Fields present in both messages, but with a different type or case.
+    ///
+    /// Every pair holds the older field first, in the order they appear in the older message.
+    pub changed: Vec<(&'a FieldInfo, &'a FieldInfo)>,
+}
+
+impl<'a> SchemaDiff<'a> {
+    /// Returns true if the messages have the same fields.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn cpp_type(datatype: &DataType, package: &str) -> String {
+    match datatype {
+        DataType::Bool => "uint8_t".into(),
+        DataType::I8(_) => "int8_t".into(),
+        DataType::I16 => "int16_t".into(),
+        DataType::I32 => "int32_t".into(),
+        DataType::I64 => "int64_t".into(),
+        DataType::U8(_) => "uint8_t".into(),
+        DataType::U16 => "uint16_t".into(),
+        DataType::U32 => "uint32_t".into(),
+        DataType::U64 => "uint64_t".into(),
+        DataType::F32 => "float".into(),
+        DataType::F64 => "double".into(),
+        DataType::String => "std::string".into(),
+        DataType::Time => "ros::Time".into(),
+        DataType::Duration => "ros::Duration".into(),
+        DataType::LocalMessage(name) => format!("{}::{}", package, name),
+        DataType::GlobalMessage(path) => format!("{}::{}", path.package(), path.name()),
+    }
+}
+
+fn source_from_fields(fields: &[FieldInfo]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}", field))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[derive(Serialize, Deserialize)]
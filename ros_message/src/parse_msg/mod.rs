@@ -9,16 +9,53 @@ static FIELD_NAME: &str = r"([a-zA-Z][a-zA-Z0-9_]*)";
 static EMPTY_BRACKETS: &str = r"\[\s*\]";
 static NUMBER_BRACKETS: &str = r"\[\s*([0-9]+)\s*\]";
 
+/// Field attributes that the parser understands.
+///
+/// None are defined by ROS yet, so every attribute is currently considered unknown.
+static KNOWN_ATTRIBUTES: &[&str] = &[];
+
+/// Options for controlling how the content of `msg` and `srv` files is parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject unknown field attributes instead of ignoring them.
+    ///
+    /// Field attributes are listed in brackets after the field name,
+    /// like `float64 x [unit="meters"]`.
+    ///
+    /// When disabled, unknown attributes are stripped from the field with a logged warning.
+    ///
+    /// Defaults to `false`.
+    pub strict: bool,
+}
+
 #[derive(Debug, PartialEq)]
 struct FieldLine {
     field_type: String,
     field_name: String,
 }
 
+#[cfg(test)]
 #[inline]
 pub fn match_lines(data: &str) -> Result<Vec<FieldInfo>> {
+    match_lines_with_options(data, ParseOptions::default())
+}
+
+/// Converts `\r\n` line endings to `\n`, and strips trailing whitespace from every line.
+///
+/// Files written on Windows would otherwise leave a `\r` at the end of every line.
+pub fn normalize_source(data: &str) -> String {
+    data.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn match_lines_with_options(data: &str, options: ParseOptions) -> Result<Vec<FieldInfo>> {
     data.split('\n')
-        .filter_map(match_line)
+        .filter_map(|line| match strip_attributes(line, options) {
+            Ok(line) => match_line(line),
+            Err(err) => Some(Err(err)),
+        })
         .collect::<Result<_>>()
 }
 
@@ -165,6 +202,51 @@ fn match_const_numeric(data: &str) -> Option<(FieldLine, String)> {
     ))
 }
 
+fn match_attributes(data: &str) -> Option<(&str, Vec<&str>)> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r"^(.*[a-zA-Z0-9_])\s*\[([^\[\]]*=[^\[\]]*)\]$").unwrap();
+    }
+    let captures = RE.captures(data)?;
+    let attributes = captures
+        .get(2)
+        .unwrap()
+        .as_str()
+        .split(',')
+        .filter_map(|attribute| attribute.split('=').next())
+        .map(str::trim)
+        .collect();
+    Some((captures.get(1).unwrap().as_str(), attributes))
+}
+
+fn strip_attributes(data: &str, options: ParseOptions) -> Result<&str> {
+    // String constants can contain anything after the equals sign, so they can't have attributes
+    if match_const_string(data.trim()).is_some() {
+        return Ok(data);
+    }
+    let (field, attributes) = match match_attributes(strip_useless(data)?) {
+        Some(v) => v,
+        None => return Ok(data),
+    };
+    for attribute in attributes {
+        if KNOWN_ATTRIBUTES.contains(&attribute) {
+            continue;
+        }
+        if options.strict {
+            return Err(Error::UnsupportedFieldAttribute {
+                attribute: attribute.into(),
+                line: data.trim().into(),
+            });
+        }
+        log::warn!(
+            "Ignoring unsupported attribute `{}` in message line `{}`",
+            attribute,
+            data.trim(),
+        );
+    }
+    Ok(field)
+}
+
 #[inline]
 fn strip_useless(data: &str) -> Result<&str> {
     Ok(data
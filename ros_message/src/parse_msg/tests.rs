@@ -1,4 +1,6 @@
 use super::*;
+use crate::DataType;
+use std::convert::TryInto;
 
 #[test]
 fn match_field_matches_legal_field() {
@@ -135,3 +137,83 @@ fn match_lines_parses_real_messages() {
         data
     );
 }
+
+#[test]
+fn match_attributes_splits_field_from_attributes() {
+    assert_eq!(
+        Some(("float64 x", vec!["unit"])),
+        match_attributes("float64 x [unit=\"meters\"]"),
+    );
+    assert_eq!(
+        Some(("float64[3]   x", vec!["unit", "frame"])),
+        match_attributes("float64[3]   x[ unit=\"m\", frame = \"map\" ]"),
+    );
+    assert_eq!(None, match_attributes("float64[3] x"));
+    assert_eq!(None, match_attributes("float64[] x"));
+    assert_eq!(None, match_attributes("int32 X=5"));
+}
+
+#[test]
+fn match_lines_ignores_unknown_attributes_in_lenient_mode() {
+    let options = ParseOptions { strict: false };
+    assert_eq!(
+        vec![
+            FieldInfo::new("float64", "x", FieldCase::Unit).unwrap(),
+            FieldInfo::new("float64", "y", FieldCase::Array(3)).unwrap(),
+            FieldInfo::new("string", "FOO", FieldCase::Const("bar [a=b]".into())).unwrap(),
+        ],
+        match_lines_with_options(
+            "float64 x [unit=\"meters\"] # comment\nfloat64[3] y\nstring FOO=bar [a=b]",
+            options,
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn match_lines_rejects_unknown_attributes_in_strict_mode() {
+    let options = ParseOptions { strict: true };
+    assert!(match_lines_with_options("float64 x\nfloat64[3] y", options).is_ok());
+    assert!(match_lines_with_options("string FOO=bar [a=b]", options).is_ok());
+    match match_lines_with_options("float64 x [unit=\"meters\"]", options) {
+        Err(Error::UnsupportedFieldAttribute { attribute, line }) => {
+            assert_eq!(attribute, "unit");
+            assert_eq!(line, "float64 x [unit=\"meters\"]");
+        }
+        other => panic!("Unexpected parse result: {:?}", other),
+    }
+}
+
+#[test]
+fn match_lines_parses_arrays_of_remote_types() {
+    let data = match_lines(
+        "geometry_msgs/Pose[] poses\ngeometry_msgs/Pose[10] fixed_poses\ngeometry_msgs/Pose [ 3 ] spaced",
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            FieldInfo::new("geometry_msgs/Pose", "poses", FieldCase::Vector).unwrap(),
+            FieldInfo::new("geometry_msgs/Pose", "fixed_poses", FieldCase::Array(10)).unwrap(),
+            FieldInfo::new("geometry_msgs/Pose", "spaced", FieldCase::Array(3)).unwrap(),
+        ],
+        data
+    );
+    for field in &data {
+        assert_eq!(
+            field.datatype(),
+            &DataType::GlobalMessage("geometry_msgs/Pose".try_into().unwrap())
+        );
+    }
+}
+
+#[test]
+fn normalize_source_strips_carriage_returns_and_trailing_whitespace() {
+    assert_eq!(
+        normalize_source("uint32 a \r\n\r\nstring FOO=bar\t\r\nfloat64 b"),
+        "uint32 a\n\nstring FOO=bar\nfloat64 b"
+    );
+    assert_eq!(
+        normalize_source("uint32 a\nuint32 b\n"),
+        "uint32 a\nuint32 b"
+    );
+}
@@ -1,3 +1,4 @@
+use crate::parse_msg::normalize_source;
 use crate::{Error, MessagePath, Msg, Result};
 use lazy_static::lazy_static;
 use regex::RegexBuilder;
@@ -97,7 +98,9 @@ impl Srv {
                 .build()
                 .expect("Invalid regex `^---$`");
         }
-        let (req, res) = match RE_SPLIT.split(source).collect::<Vec<_>>().as_slice() {
+        // The separator wouldn't match with `\r\n` line endings.
+        let source = normalize_source(source);
+        let (req, res) = match RE_SPLIT.split(&source).collect::<Vec<_>>().as_slice() {
             &[req] => (req, ""),
             &[req, res] => (req, res),
             &[] => {
@@ -1,4 +1,4 @@
-use crate::{DataType, MessagePath};
+use crate::{DataType, Duration, MessagePath, MessageValue, Time, Value};
 use std::collections::HashMap;
 
 #[test]
@@ -35,6 +35,20 @@ fn md5_string_is_correct() {
     );
 }
 
+#[test]
+fn message_path_is_only_known_for_global_messages() {
+    let path = MessagePath::new("p1", "xx").expect("Unexpected bad message path");
+    assert_eq!(
+        DataType::GlobalMessage(path.clone()).message_path(),
+        Some(&path)
+    );
+    assert_eq!(DataType::LocalMessage("xx".into()).message_path(), None);
+    assert_eq!(DataType::Time.message_path(), None);
+    assert!(DataType::Time.is_builtin());
+    assert!(!DataType::LocalMessage("xx".into()).is_builtin());
+    assert!(!DataType::GlobalMessage(path).is_builtin());
+}
+
 #[test]
 fn serialize_as_string() {
     assert_eq!(serde_json::to_string(&DataType::I64).unwrap(), "\"int64\"");
@@ -82,3 +96,59 @@ fn deserialize_from_string() {
         DataType::GlobalMessage(MessagePath::new("p1", "xx").expect("Unexpected bad message path")),
     );
 }
+
+#[test]
+fn default_value_matches_data_type() {
+    let cases = vec![
+        ("bool", Value::Bool(false)),
+        ("int8", Value::I8(0)),
+        ("byte", Value::I8(0)),
+        ("int16", Value::I16(0)),
+        ("int32", Value::I32(0)),
+        ("int64", Value::I64(0)),
+        ("uint8", Value::U8(0)),
+        ("char", Value::U8(0)),
+        ("uint16", Value::U16(0)),
+        ("uint32", Value::U32(0)),
+        ("uint64", Value::U64(0)),
+        ("float32", Value::F32(0.0)),
+        ("float64", Value::F64(0.0)),
+        ("string", Value::String(String::new())),
+        ("time", Value::Time(Time::from_nanos(0))),
+        ("duration", Value::Duration(Duration::from_nanos(0))),
+        ("Header", Value::Message(MessageValue::new())),
+        ("Point", Value::Message(MessageValue::new())),
+        ("geometry_msgs/Point", Value::Message(MessageValue::new())),
+    ];
+    for (datatype, value) in cases {
+        assert_eq!(DataType::parse(datatype).unwrap().default_value(), value);
+    }
+}
+
+#[test]
+fn byte_size_matches_wire_format() {
+    let cases = vec![
+        ("bool", Some(1)),
+        ("int8", Some(1)),
+        ("byte", Some(1)),
+        ("int16", Some(2)),
+        ("int32", Some(4)),
+        ("int64", Some(8)),
+        ("uint8", Some(1)),
+        ("char", Some(1)),
+        ("uint16", Some(2)),
+        ("uint32", Some(4)),
+        ("uint64", Some(8)),
+        ("float32", Some(4)),
+        ("float64", Some(8)),
+        ("string", None),
+        ("time", Some(8)),
+        ("duration", Some(8)),
+        ("Header", None),
+        ("Point", None),
+        ("geometry_msgs/Point", None),
+    ];
+    for (datatype, size) in cases {
+        assert_eq!(DataType::parse(datatype).unwrap().byte_size(), size);
+    }
+}
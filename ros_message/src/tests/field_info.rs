@@ -1,4 +1,4 @@
-use crate::{FieldCase, FieldInfo, MessagePath, Value};
+use crate::{DataType, FieldCase, FieldInfo, MessagePath, Value};
 use std::collections::HashMap;
 
 #[test]
@@ -273,3 +273,31 @@ fn deserialize_field_info_ensures_correct_const_value() {
     )
     .is_err());
 }
+
+#[test]
+fn builder_methods_match_constructor() {
+    let path = MessagePath::new("geometry_msgs", "Point").unwrap();
+    assert_eq!(
+        FieldInfo::from_datatype(DataType::GlobalMessage(path))
+            .with_name("points")
+            .with_case(FieldCase::Array(3)),
+        FieldInfo::new("geometry_msgs/Point", "points", FieldCase::Array(3)).unwrap()
+    );
+
+    let constant = FieldInfo::from_datatype(DataType::F32)
+        .with_case(FieldCase::Const("1.5".into()))
+        .with_name("SCALE");
+    assert_eq!(constant.const_value(), Some(&Value::F32(1.5)));
+    assert_eq!(format!("{}", constant), "float32 SCALE=1.5");
+
+    let unit = constant.with_case(FieldCase::Unit);
+    assert_eq!(unit.const_value(), None);
+    assert!(!unit.is_constant());
+}
+
+#[test]
+#[should_panic]
+fn with_case_panics_on_invalid_constant() {
+    FieldInfo::from_datatype(DataType::U8(crate::U8Variant::Uint8))
+        .with_case(FieldCase::Const("-1".into()));
+}
@@ -0,0 +1,137 @@
+use crate::{from_json, to_json, Duration, Error, MessagePath, Msg, Time, Value};
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+fn messages() -> (Msg, HashMap<MessagePath, Msg>) {
+    let pose = Msg::new(
+        "foo/Pose".try_into().unwrap(),
+        r#"
+        Header header
+        Point[2] corners
+        uint8 KIND=3
+        uint8 kind
+        float32 score
+        duration age
+        "#,
+    )
+    .unwrap();
+    let point = Msg::new("foo/Point".try_into().unwrap(), "float64 x\nfloat64 y").unwrap();
+    let header = Msg::new(
+        "std_msgs/Header".try_into().unwrap(),
+        "uint32 seq\ntime stamp\nstring frame_id",
+    )
+    .unwrap();
+
+    let mut messages = HashMap::new();
+    messages.insert(point.path().clone(), point);
+    messages.insert(header.path().clone(), header);
+    (pose, messages)
+}
+
+#[test]
+fn from_json_fills_in_nested_messages() {
+    let (pose, messages) = messages();
+    let input = json!({
+        "header": {"seq": 4, "stamp": {"sec": 10, "nsec": 20}, "frame_id": "map"},
+        "corners": [{"x": 1.5, "y": 2}, {"x": -1}],
+        "kind": 255,
+        "score": 0.5,
+        "age": {"sec": -1, "nsec": -500},
+    });
+
+    let message = from_json(input.clone(), &pose, &messages).unwrap();
+
+    let header = message["header"].as_map().unwrap();
+    assert_eq!(header["stamp"], Value::Time(Time { sec: 10, nsec: 20 }));
+    assert_eq!(header["frame_id"], Value::String("map".into()));
+    let corners = message["corners"].as_slice().unwrap();
+    assert_eq!(corners[0].as_map().unwrap()["y"], Value::F64(2.0));
+    assert_eq!(corners[1].as_map().unwrap()["y"], Value::F64(0.0));
+
+    let defaults = from_json(json!({}), &pose, &messages).unwrap();
+    let header = defaults["header"].as_map().unwrap();
+    assert_eq!(header["frame_id"], Value::String("".into()));
+    let corners = defaults["corners"].as_slice().unwrap();
+    assert_eq!(corners.len(), 2);
+    assert_eq!(corners[1].as_map().unwrap()["x"], Value::F64(0.0));
+    assert_eq!(message["kind"], Value::U8(255));
+    assert_eq!(message["score"], Value::F32(0.5));
+    assert_eq!(
+        message["age"],
+        Value::Duration(Duration {
+            sec: -1,
+            nsec: -500
+        })
+    );
+    assert!(!message.contains_key("KIND"));
+
+    let mut expected = input;
+    expected["corners"][1]["y"] = json!(0.0);
+    expected["corners"][0]["y"] = json!(2.0);
+    expected["corners"][1]["x"] = json!(-1.0);
+    assert_eq!(to_json(&message), expected);
+}
+
+#[test]
+fn from_json_reports_where_the_mismatch_is() {
+    let (pose, messages) = messages();
+    let error = |input| from_json(input, &pose, &messages).unwrap_err().to_string();
+
+    assert_eq!(
+        error(json!({"kind": 256})),
+        "JSON value at `foo/Pose.kind` doesn't fit the message definition, \
+         expected uint8, found 256",
+    );
+    assert_eq!(
+        error(json!({"corners": [{"x": 1}, {"x": "far"}]})),
+        "JSON value at `foo/Pose.corners[1].x` doesn't fit the message definition, \
+         expected float64, found \"far\"",
+    );
+    assert_eq!(
+        error(json!({"corners": [{"x": 1}]})),
+        "JSON value at `foo/Pose.corners` doesn't fit the message definition, \
+         expected 2 items, found 1",
+    );
+    assert_eq!(
+        error(json!({"header": {"sequence": 1}})),
+        "JSON value at `foo/Pose.header.sequence` doesn't fit the message definition, \
+         message std_msgs/Header has no such field",
+    );
+    assert_eq!(
+        error(json!([1, 2])),
+        "JSON value at `foo/Pose` doesn't fit the message definition, \
+         expected message foo/Pose, found [1,2]",
+    );
+}
+
+#[test]
+fn from_json_requires_embedded_definitions() {
+    let (pose, _) = messages();
+    match from_json(json!({}), &pose, &HashMap::new()) {
+        Err(Error::MessageDependencyMissing { package, name }) => {
+            assert_eq!(package, "std_msgs");
+            assert_eq!(name, "Header");
+        }
+        other => panic!("Expected missing dependency, got {:?}", other),
+    }
+}
+
+#[test]
+fn to_json_replaces_unrepresentable_floats_with_null() {
+    let mut message = HashMap::new();
+    message.insert("nan".to_owned(), Value::F64(f64::NAN));
+    assert_eq!(to_json(&message), json!({ "nan": null }));
+}
+
+#[test]
+fn from_json_reads_null_floats_as_nan() {
+    let msg = Msg::new("foo/Floats".try_into().unwrap(), "float32 a\nfloat64 b").unwrap();
+    let mut message = HashMap::new();
+    message.insert("a".to_owned(), Value::F32(f32::NAN));
+    message.insert("b".to_owned(), Value::F64(f64::INFINITY));
+
+    let message = from_json(to_json(&message), &msg, &HashMap::new()).unwrap();
+    assert!(matches!(message["a"], Value::F32(v) if v.is_nan()));
+    assert!(matches!(message["b"], Value::F64(v) if v.is_nan()));
+}
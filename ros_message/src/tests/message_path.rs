@@ -64,3 +64,45 @@ fn deserialize_checks_if_package_name_is_valid() {
     assert!(serde_json::from_str::<MessagePath>("\"foo_123/MessageName\"").is_ok());
     assert!(serde_json::from_str::<MessagePath>("\"Foo_123/MessageName\"").is_err());
 }
+
+#[test]
+fn matches_glob_keeps_wildcards_within_package_or_name() {
+    let path = MessagePath::new("geometry_msgs", "PoseStamped").unwrap();
+    for pattern in [
+        "geometry_msgs/PoseStamped",
+        "geometry_msgs/*",
+        "*/PoseStamped",
+        "*/*",
+        "*_msgs/Pose*",
+        "geometry_msgs/*Stamped",
+        "geometry_msgs/Pose?tamped",
+        "*/*Pose*",
+    ] {
+        assert!(path.matches_glob(pattern), "{} should match", pattern);
+    }
+    for pattern in [
+        "",
+        "*",
+        "geometry_msgs",
+        "geometry_msgs*",
+        "*Stamped",
+        "geometry*Stamped",
+        "geometry_msgs/posestamped",
+        "Geometry_msgs/*",
+        "/PoseStamped",
+        "geometry_msgs/Pose",
+        "geometry_msgs/Pose?",
+    ] {
+        assert!(!path.matches_glob(pattern), "{} shouldn't match", pattern);
+    }
+}
+
+#[test]
+fn matches_prefix_compares_whole_package() {
+    let path = MessagePath::new("geometry_msgs", "PoseStamped").unwrap();
+    assert!(path.matches_prefix("geometry_msgs"));
+    assert!(!path.matches_prefix("geometry"));
+    assert!(!path.matches_prefix("Geometry_msgs"));
+    assert!(!path.matches_prefix("geometry_msgs/"));
+    assert!(!path.matches_prefix(""));
+}
@@ -1,6 +1,9 @@
 mod data_type;
 mod field_info;
+#[cfg(feature = "json")]
+mod json;
 mod message_path;
 mod msg;
+mod srv;
 mod time;
 mod value;
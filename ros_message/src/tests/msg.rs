@@ -1,7 +1,62 @@
-use crate::{FieldCase, FieldInfo, MessagePath, Msg, Value};
+use crate::{Error, FieldCase, FieldInfo, MessagePath, Msg, Value};
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 
+#[test]
+fn md5sum_computes_dependency_hashes() {
+    let mut messages = HashMap::new();
+    for (path, source) in [
+        (
+            "geometry_msgs/Point",
+            include_str!("../../../msg_examples/geometry_msgs/msg/Point.msg"),
+        ),
+        (
+            "std_msgs/ColorRGBA",
+            include_str!("../../../msg_examples/std_msgs/msg/ColorRGBA.msg"),
+        ),
+        (
+            "std_msgs/Header",
+            include_str!("../../../msg_examples/std_msgs/msg/Header.msg"),
+        ),
+    ] {
+        let path: MessagePath = path.try_into().unwrap();
+        messages.insert(path.clone(), Msg::new(path, source).unwrap());
+    }
+    let marker = Msg::new(
+        "visualization_msgs/ImageMarker".try_into().unwrap(),
+        include_str!("../../../msg_examples/visualization_msgs/msg/ImageMarker.msg"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        messages[&"std_msgs/Header".try_into().unwrap()]
+            .md5sum(&messages)
+            .unwrap(),
+        "2176decaecbce78abc3b96ef049fabed",
+    );
+    assert_eq!(
+        marker.md5sum(&messages).unwrap(),
+        "1de93c67ec8858b831025a08fbf1b35c",
+    );
+
+    messages.remove(&"std_msgs/ColorRGBA".try_into().unwrap());
+    assert!(marker.md5sum(&messages).is_err());
+}
+
+#[test]
+fn md5sum_fails_on_dependency_cycle() {
+    let a = Msg::new("foo/A".try_into().unwrap(), "foo/B b").unwrap();
+    let b = Msg::new("foo/B".try_into().unwrap(), "foo/A a").unwrap();
+    let mut messages = HashMap::new();
+    messages.insert(a.path().clone(), a.clone());
+    messages.insert(b.path().clone(), b);
+
+    assert!(matches!(
+        a.md5sum(&messages),
+        Err(Error::MessageDependencyCycle { package, name }) if package == "foo" && name == "A",
+    ));
+}
+
 #[test]
 fn md5_string_is_correct() {
     assert_eq!(
@@ -192,6 +247,42 @@ fn constructor_parses_real_message() {
         .contains(&MessagePath::new("std_msgs", "Header").expect("Unexpected bad message path")));
 }
 
+#[test]
+fn unqualified_header_resolves_to_std_msgs_header() {
+    let header = MessagePath::new("std_msgs", "Header").expect("Unexpected bad message path");
+    let data = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        Header header
+        Header[] headers
+        foo/Header own_header
+        "#,
+    )
+    .unwrap();
+
+    let fields = data.fields();
+    assert_eq!(fields[0].datatype().message_path(), Some(&header));
+    assert_eq!(fields[1].datatype().message_path(), Some(&header));
+    assert_eq!(
+        fields[2].datatype().message_path(),
+        Some(&MessagePath::new("foo", "Header").expect("Unexpected bad message path")),
+    );
+    assert_eq!(
+        get_dependency_set(&data),
+        vec![
+            header.clone(),
+            MessagePath::new("foo", "Header").expect("Unexpected bad message path"),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    // Same inside of `std_msgs` itself.
+    let data = Msg::new("std_msgs/Bar".try_into().unwrap(), "Header header").unwrap();
+    assert_eq!(data.fields()[0].datatype().message_path(), Some(&header));
+    assert!(data.has_header());
+}
+
 #[test]
 fn has_header_checks_if_there_is_a_header_in_the_message_root() {
     let without_header = Msg::new(
@@ -334,3 +425,282 @@ fn deserialize_rejects_bad_name_or_source() {
             .is_err(),
     );
 }
+
+#[test]
+fn strip_constants_keeps_variable_fields_and_changes_md5() {
+    let msg = Msg::new(
+        "benchmark_msgs/Overall".try_into().unwrap(),
+        include_str!("../../../msg_examples/benchmark_msgs/msg/Overall.msg"),
+    )
+    .unwrap();
+    let stripped = msg.strip_constants();
+
+    assert_eq!(stripped.path(), msg.path());
+    assert!(stripped.constants().is_empty());
+    assert_eq!(
+        stripped.fields(),
+        msg.fields()
+            .iter()
+            .filter(|field| !field.is_constant())
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        Msg::new(stripped.path().clone(), stripped.source()).unwrap(),
+        stripped,
+    );
+
+    let hashes = msg
+        .dependencies()
+        .into_iter()
+        .map(|path| (path, "hash".to_owned()))
+        .collect::<HashMap<_, _>>();
+    assert_ne!(
+        msg.calculate_md5(&hashes).unwrap(),
+        stripped.calculate_md5(&hashes).unwrap(),
+    );
+}
+
+#[test]
+fn from_str_recovering_matches_new_for_valid_source() {
+    let source = include_str!("../../../msg_examples/benchmark_msgs/msg/Overall.msg");
+    let msg = Msg::new("benchmark_msgs/Overall".try_into().unwrap(), source).unwrap();
+    let (recovered, errors) =
+        Msg::from_str_recovering("benchmark_msgs/Overall".try_into().unwrap(), source);
+
+    assert!(errors.is_empty());
+    assert_eq!(recovered, msg);
+}
+
+#[test]
+fn from_str_recovering_collects_all_errors() {
+    let (msg, errors) = Msg::from_str_recovering(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        uint32 a
+        uint32[-1] bad_array
+        float64 b # comment
+        not a field
+        uint8 C=3
+        "#,
+    );
+
+    assert_eq!(
+        msg.fields(),
+        &[
+            FieldInfo::new("uint32", "a", FieldCase::Unit).unwrap(),
+            FieldInfo::new("float64", "b", FieldCase::Unit).unwrap(),
+            FieldInfo::new("uint8", "C", FieldCase::Const("3".into())).unwrap(),
+        ],
+    );
+    assert_eq!(errors.len(), 2);
+    assert_eq!(Msg::new(msg.path().clone(), msg.source()).unwrap(), msg);
+}
+
+#[test]
+fn common_fields_with_requires_matching_name_and_type() {
+    let first = Msg::new(
+        "foo/First".try_into().unwrap(),
+        r#"
+        uint32 a
+        float64 b
+        uint8[] c
+        uint8[4] d
+        string NAME=first
+        uint8 FLAG=1
+        "#,
+    )
+    .unwrap();
+    let second = Msg::new(
+        "foo/Second".try_into().unwrap(),
+        r#"
+        uint32 a
+        float32 b
+        uint8[4] c
+        uint8[4] d
+        string NAME=second
+        uint8 FLAG=1
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        first.common_fields_with(&second),
+        vec![
+            &FieldInfo::new("uint32", "a", FieldCase::Unit).unwrap(),
+            &FieldInfo::new("uint8", "d", FieldCase::Array(4)).unwrap(),
+            &FieldInfo::new("uint8", "FLAG", FieldCase::Const("1".into())).unwrap(),
+        ],
+    );
+    assert_eq!(first.common_fields_with(&first).len(), first.fields().len());
+}
+
+#[test]
+fn add_and_remove_field_keep_source_in_sync() {
+    let mut msg = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        Header header
+        uint32 a
+        string NAME=bar
+        "#,
+    )
+    .unwrap();
+
+    msg.add_field(FieldInfo::new("geometry_msgs/Point", "p", FieldCase::Array(3)).unwrap())
+        .unwrap();
+    assert!(msg
+        .add_field(FieldInfo::new("float64", "NAME", FieldCase::Unit).unwrap())
+        .is_err());
+    assert_eq!(
+        msg.remove_field("a"),
+        Some(FieldInfo::new("uint32", "a", FieldCase::Unit).unwrap()),
+    );
+    assert_eq!(msg.remove_field("missing"), None);
+
+    assert_eq!(
+        msg.fields(),
+        &[
+            FieldInfo::new("Header", "header", FieldCase::Unit).unwrap(),
+            FieldInfo::new("string", "NAME", FieldCase::Const("bar".into())).unwrap(),
+            FieldInfo::new("geometry_msgs/Point", "p", FieldCase::Array(3)).unwrap(),
+        ],
+    );
+    assert_eq!(Msg::new(msg.path().clone(), msg.source()).unwrap(), msg);
+}
+
+#[test]
+fn to_cpp_declaration_maps_types_and_serialization() {
+    let msg = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        Header header
+        bool flag
+        byte[16] b
+        char c
+        time stamp
+        duration[] durations
+        geometry_msgs/Point[] points
+        Baz baz
+        string NAME=some "quoted" text
+        int16 OFFSET=-3
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        msg.to_cpp_declaration(),
+        r#"namespace foo {
+
+struct Bar {
+  std_msgs::Header header;
+  uint8_t flag;
+  boost::array<int8_t, 16> b;
+  uint8_t c;
+  ros::Time stamp;
+  std::vector<ros::Duration> durations;
+  std::vector<geometry_msgs::Point> points;
+  foo::Baz baz;
+  static constexpr const char* NAME = "some \"quoted\" text";
+  static constexpr int16_t OFFSET = -3;
+};
+
+}  // namespace foo
+
+namespace ros {
+namespace serialization {
+
+template <>
+struct Serializer<foo::Bar> {
+  template <typename Stream, typename T>
+  inline static void allInOne(Stream& stream, T m) {
+    stream.next(m.header);
+    stream.next(m.flag);
+    stream.next(m.b);
+    stream.next(m.c);
+    stream.next(m.stamp);
+    stream.next(m.durations);
+    stream.next(m.points);
+    stream.next(m.baz);
+  }
+
+  ROS_DECLARE_ALLINONE_SERIALIZER
+};
+
+}  // namespace serialization
+}  // namespace ros
+"#,
+    );
+}
+
+#[test]
+fn crlf_source_matches_lf_source() {
+    let lf = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        "# comment\nuint32 a\nstring FOO=some text\nfloat64[] b  \n",
+    )
+    .unwrap();
+    let crlf = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        "# comment\r\nuint32 a\r\nstring FOO=some text\r\nfloat64[] b  \r\n",
+    )
+    .unwrap();
+    assert_eq!(crlf, lf);
+    assert_eq!(
+        crlf.source(),
+        "# comment\nuint32 a\nstring FOO=some text\nfloat64[] b"
+    );
+    assert_eq!(crlf.constants()["FOO"], Value::String("some text".into()));
+}
+
+#[test]
+fn diff_schemas_matches_fields_by_name() {
+    let old = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        uint32 a
+        uint8[] b
+        string NAME=old
+        float32 removed
+        "#,
+    )
+    .unwrap();
+    let new = Msg::new(
+        "foo/Bar".try_into().unwrap(),
+        r#"
+        uint8[4] b
+        float32 added
+        uint32 a
+        string NAME=new
+        "#,
+    )
+    .unwrap();
+
+    let diff = old.diff_schemas(&new);
+    assert_eq!(
+        diff.added,
+        vec![&FieldInfo::new("float32", "added", FieldCase::Unit).unwrap()],
+    );
+    assert_eq!(
+        diff.removed,
+        vec![&FieldInfo::new("float32", "removed", FieldCase::Unit).unwrap()],
+    );
+    assert_eq!(
+        diff.changed,
+        vec![
+            (
+                &FieldInfo::new("uint8", "b", FieldCase::Vector).unwrap(),
+                &FieldInfo::new("uint8", "b", FieldCase::Array(4)).unwrap(),
+            ),
+            (
+                &FieldInfo::new("string", "NAME", FieldCase::Const("old".into())).unwrap(),
+                &FieldInfo::new("string", "NAME", FieldCase::Const("new".into())).unwrap(),
+            ),
+        ],
+    );
+    assert!(!diff.is_empty());
+
+    let reverse = new.diff_schemas(&old);
+    assert_eq!(reverse.added, diff.removed);
+    assert_eq!(reverse.removed, diff.added);
+}
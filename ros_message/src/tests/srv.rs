@@ -0,0 +1,19 @@
+use crate::{Msg, Srv};
+use std::convert::TryInto;
+
+#[test]
+fn crlf_source_is_split_into_request_and_response() {
+    let srv = Srv::new(
+        "foo/Baz".try_into().unwrap(),
+        "uint32 a\r\nuint32 b\r\n---\r\nuint64 sum\r\n",
+    )
+    .unwrap();
+    assert_eq!(
+        srv.request(),
+        &Msg::new("foo/BazReq".try_into().unwrap(), "uint32 a\nuint32 b").unwrap()
+    );
+    assert_eq!(
+        srv.response(),
+        &Msg::new("foo/BazRes".try_into().unwrap(), "uint64 sum").unwrap()
+    );
+}
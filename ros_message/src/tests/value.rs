@@ -1,4 +1,7 @@
-use crate::{MessageValue, Time, Value};
+use crate::{
+    ConversionError, Error, MessageValue, MessageValueExt, Msg, Time, Value, ValuePattern,
+};
+use std::convert::TryInto;
 
 #[test]
 fn display() {
@@ -79,3 +82,383 @@ poses:
       z: 10"#
     )
 }
+
+#[test]
+fn apply_visits_only_top_level_fields() {
+    let mut inner = MessageValue::new();
+    inner.insert("a".into(), Value::U32(1));
+
+    let mut message = MessageValue::new();
+    message.insert("a".into(), Value::U32(2));
+    message.insert("inner".into(), Value::Message(inner.clone()));
+
+    message.apply(|name, value| match value {
+        Value::U32(v) => Value::U32(v * 10),
+        Value::Message(_) => Value::String(name.into()),
+        v => v,
+    });
+
+    assert_eq!(message["a"], Value::U32(20));
+    assert_eq!(message["inner"], Value::String("inner".into()));
+}
+
+#[test]
+fn apply_recursive_descends_into_messages_and_arrays_of_messages() {
+    let mut pose = MessageValue::new();
+    pose.insert("x".into(), Value::F64(1.0));
+    pose.insert("y".into(), Value::F64(2.0));
+
+    let mut message = MessageValue::new();
+    message.insert("pose".into(), Value::Message(pose.clone()));
+    message.insert(
+        "poses".into(),
+        Value::Array(vec![Value::Message(pose.clone()), Value::Message(pose)]),
+    );
+    message.insert("values".into(), Value::Array(vec![Value::F64(3.0)]));
+
+    message.apply_recursive(|name, value| match (name, value) {
+        ("x", Value::F64(v)) => Value::F64(v + 10.0),
+        ("values", Value::Array(v)) => Value::U32(v.len() as u32),
+        (_, v) => v,
+    });
+
+    let mut expected_pose = MessageValue::new();
+    expected_pose.insert("x".into(), Value::F64(11.0));
+    expected_pose.insert("y".into(), Value::F64(2.0));
+
+    assert_eq!(message["pose"], Value::Message(expected_pose.clone()));
+    assert_eq!(
+        message["poses"],
+        Value::Array(vec![
+            Value::Message(expected_pose.clone()),
+            Value::Message(expected_pose),
+        ]),
+    );
+    assert_eq!(message["values"], Value::U32(1));
+}
+
+#[test]
+fn drain_field_moves_field_out_of_message() {
+    let mut inner = MessageValue::new();
+    inner.insert("a".into(), Value::U32(1));
+
+    let mut message = MessageValue::new();
+    message.insert("inner".into(), Value::Message(inner.clone()));
+    message.insert("b".into(), Value::Bool(true));
+
+    assert_eq!(message.drain_field("inner"), Some(Value::Message(inner)));
+    assert_eq!(message.drain_field("inner"), None);
+    assert_eq!(message.len(), 1);
+    assert_eq!(message["b"], Value::Bool(true));
+}
+
+#[test]
+fn into_typed_converts_matching_values() {
+    assert_eq!(Value::Bool(true).into_typed::<bool>(), Ok(true));
+    assert_eq!(Value::I64(-5).into_typed::<i64>(), Ok(-5));
+    assert_eq!(Value::F32(1.5).into_typed::<f32>(), Ok(1.5));
+    assert_eq!(
+        Value::Time(Time::from_nanos(5)).into_typed::<Time>(),
+        Ok(Time::from_nanos(5)),
+    );
+    assert_eq!(
+        Value::from([1i16, 2, 3]).into_typed::<[i16; 3]>(),
+        Ok([1, 2, 3]),
+    );
+    assert_eq!(
+        Value::from(vec!["a".to_owned(), "b".to_owned()]).into_typed::<Vec<String>>(),
+        Ok(vec!["a".to_owned(), "b".to_owned()]),
+    );
+}
+
+#[test]
+fn into_typed_reports_mismatched_types() {
+    assert_eq!(
+        Value::U8(3).into_typed::<bool>(),
+        Err(ConversionError {
+            expected: "bool",
+            found: "uint8",
+        }),
+    );
+    assert_eq!(
+        Value::from(vec![1i32, 2]).into_typed::<[i32; 3]>(),
+        Err(ConversionError {
+            expected: "[i32; 3]",
+            found: "array",
+        }),
+    );
+    assert_eq!(
+        Value::from(vec![Value::U8(1), Value::String("2".into())]).into_typed::<Vec<u8>>(),
+        Err(ConversionError {
+            expected: "u8",
+            found: "string",
+        }),
+    );
+}
+
+#[test]
+fn as_array_of_converts_every_element() {
+    let strings = Value::from(vec!["a".to_owned(), "b".to_owned()]);
+    assert_eq!(
+        strings.as_array_of::<String>(),
+        Some(vec!["a".to_owned(), "b".to_owned()]),
+    );
+    assert_eq!(Value::Array(vec![]).as_array_of::<f64>(), Some(vec![]));
+
+    let nested = Value::from(vec![vec![1u8, 2], vec![3]]);
+    assert_eq!(
+        nested.as_array_of::<Vec<u8>>(),
+        Some(vec![vec![1, 2], vec![3]]),
+    );
+
+    let mixed = Value::from(vec![Value::U8(1), Value::String("2".into())]);
+    assert_eq!(mixed.as_array_of::<u8>(), None);
+    assert_eq!(Value::String("ab".into()).as_array_of::<String>(), None);
+}
+
+#[test]
+fn matches_checks_value_against_pattern() {
+    let mut message = MessageValue::new();
+    message.insert("x".into(), Value::F64(1.0));
+    let values = [
+        Value::Bool(true),
+        Value::I8(-4),
+        Value::U64(7),
+        Value::F64(0.25),
+        Value::String("hello world".into()),
+        Value::Time(Time::from_nanos(5)),
+        Value::Array(vec![Value::U8(1)]),
+        Value::Message(message),
+    ];
+
+    let matching = |pattern: ValuePattern| {
+        values
+            .iter()
+            .map(|value| value.matches(&pattern))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        matching(ValuePattern::AnyNumeric),
+        vec![false, true, true, true, false, false, false, false],
+    );
+    assert_eq!(
+        matching(ValuePattern::NumericRange(-4.0, 1.0)),
+        vec![false, true, false, true, false, false, false, false],
+    );
+    assert_eq!(
+        matching(ValuePattern::BoolValue(true)),
+        vec![true, false, false, false, false, false, false, false],
+    );
+    assert_eq!(
+        matching(ValuePattern::StringContains("lo w".into())),
+        vec![false, false, false, false, true, false, false, false],
+    );
+    assert_eq!(
+        matching(ValuePattern::AnyArray),
+        vec![false, false, false, false, false, false, true, false],
+    );
+    assert_eq!(
+        matching(ValuePattern::AnyMessage),
+        vec![false, false, false, false, false, false, false, true],
+    );
+}
+
+#[test]
+fn flatten_joins_paths_of_nested_messages() {
+    let mut header = MessageValue::new();
+    header.insert("seq".into(), Value::U32(4));
+    header.insert("frame_id".into(), Value::String("map".into()));
+
+    let mut pose = MessageValue::new();
+    pose.insert("header".into(), Value::Message(header));
+    pose.insert("x".into(), Value::F64(1.0));
+
+    let mut message = MessageValue::new();
+    message.insert("pose".into(), Value::Message(pose.clone()));
+    message.insert(
+        "poses".into(),
+        Value::Array(vec![Value::Message(pose.clone()), Value::Message(pose)]),
+    );
+    message.insert("empty".into(), Value::Message(MessageValue::new()));
+
+    assert_eq!(
+        Value::Message(message).flatten("/"),
+        vec![
+            ("pose/header/frame_id".into(), Value::String("map".into())),
+            ("pose/header/seq".into(), Value::U32(4)),
+            ("pose/x".into(), Value::F64(1.0)),
+            (
+                "poses/0/header/frame_id".into(),
+                Value::String("map".into())
+            ),
+            ("poses/0/header/seq".into(), Value::U32(4)),
+            ("poses/0/x".into(), Value::F64(1.0)),
+            (
+                "poses/1/header/frame_id".into(),
+                Value::String("map".into())
+            ),
+            ("poses/1/header/seq".into(), Value::U32(4)),
+            ("poses/1/x".into(), Value::F64(1.0)),
+        ],
+    );
+}
+
+#[test]
+fn type_eq_ignores_contained_values() {
+    let mut point = MessageValue::new();
+    point.insert("x".into(), Value::F64(1.0));
+    point.insert("label".into(), Value::String("a".into()));
+
+    let mut other_point = MessageValue::new();
+    other_point.insert("x".into(), Value::F64(-3.0));
+    other_point.insert("label".into(), Value::String("b".into()));
+
+    let mut wrong_point = other_point.clone();
+    wrong_point.insert("x".into(), Value::F32(-3.0));
+
+    let points = Value::Array(vec![Value::Message(point.clone())]);
+    let other_points = Value::Array(vec![
+        Value::Message(other_point.clone()),
+        Value::Message(point.clone()),
+    ]);
+
+    assert!(Value::Message(point.clone()).type_eq(&Value::Message(other_point)));
+    assert!(!Value::Message(point).type_eq(&Value::Message(wrong_point.clone())));
+    assert!(points.type_eq(&other_points));
+    assert!(points.type_eq(&Value::Array(vec![])));
+    assert!(!points.type_eq(&Value::Array(vec![Value::Message(wrong_point)])));
+    assert!(!points.type_eq(&Value::F64(1.0)));
+    assert!(Value::String("a".into()).type_eq(&Value::String("b".into())));
+}
+
+#[test]
+fn extend_array_checks_element_types() {
+    let mut point = MessageValue::new();
+    point.insert("x".into(), Value::F64(1.0));
+    let mut wrong_point = MessageValue::new();
+    wrong_point.insert("x".into(), Value::F32(1.0));
+
+    let mut points = Value::Array(vec![]);
+    points
+        .append_to_array(Value::Message(point.clone()))
+        .unwrap();
+    points
+        .extend_array(vec![
+            Value::Message(point.clone()),
+            Value::Message(point.clone()),
+        ])
+        .unwrap();
+    assert_eq!(points.as_slice().map(<[Value]>::len), Some(3));
+
+    let err = points
+        .extend_array(vec![Value::Message(point), Value::Message(wrong_point)])
+        .unwrap_err();
+    assert!(matches!(err, Error::ArrayElementMismatch { .. }));
+    assert_eq!(points.as_slice().map(<[Value]>::len), Some(3));
+
+    let mut empty = Value::Array(vec![]);
+    let err = empty
+        .extend_array(vec![Value::U8(1), Value::I8(2)])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ArrayElementMismatch {
+            expected: "uint8",
+            found: "int8",
+        }
+    ));
+    assert_eq!(empty, Value::Array(vec![]));
+
+    let err = Value::String("a".into())
+        .append_to_array(Value::String("b".into()))
+        .unwrap_err();
+    assert!(matches!(err, Error::NotAnArray { found: "string" }));
+}
+
+#[test]
+fn schema_hash_depends_on_names_and_types_only() {
+    let mut point = MessageValue::new();
+    point.insert("x".into(), Value::F64(1.0));
+    point.insert("y".into(), Value::F64(2.0));
+
+    let mut message = MessageValue::new();
+    message.insert("point".into(), Value::Message(point.clone()));
+    message.insert("points".into(), Value::Array(vec![Value::Message(point)]));
+    message.insert("count".into(), Value::U32(3));
+
+    let mut other_point = MessageValue::new();
+    other_point.insert("x".into(), Value::F64(-1.0));
+    other_point.insert("y".into(), Value::F64(-2.0));
+
+    let mut same_schema = MessageValue::new();
+    same_schema.insert("count".into(), Value::U32(7));
+    same_schema.insert("point".into(), Value::Message(other_point.clone()));
+    same_schema.insert(
+        "points".into(),
+        Value::Array(vec![
+            Value::Message(other_point.clone()),
+            Value::Message(other_point.clone()),
+        ]),
+    );
+    assert_eq!(message.schema_hash(), same_schema.schema_hash());
+
+    let mut renamed = same_schema.clone();
+    let count = renamed.drain_field("count").unwrap();
+    renamed.insert("total".into(), count);
+    assert_ne!(message.schema_hash(), renamed.schema_hash());
+
+    let mut nested_change = other_point;
+    nested_change.insert("y".into(), Value::F32(-2.0));
+    let mut changed = same_schema;
+    changed.insert("point".into(), Value::Message(nested_change));
+    assert_ne!(message.schema_hash(), changed.schema_hash());
+}
+
+#[test]
+fn apply_schema_defaults_fills_in_missing_fields_only() {
+    let schema = Msg::new(
+        "foo_msgs/Sample".try_into().unwrap(),
+        "int32 LIMIT=5\nstring name\nuint8[] data\nfloat32[2] range\ngeometry_msgs/Point point\nuint32 count",
+    )
+    .unwrap();
+
+    let mut message = MessageValue::new();
+    message.insert("name".into(), Value::String("sample".into()));
+    message.insert("count".into(), Value::I64(-1));
+    message.apply_schema_defaults(&schema);
+
+    let mut expected = MessageValue::new();
+    expected.insert("name".into(), Value::String("sample".into()));
+    expected.insert("data".into(), Value::Array(vec![]));
+    expected.insert(
+        "range".into(),
+        Value::Array(vec![Value::F32(0.0), Value::F32(0.0)]),
+    );
+    expected.insert("point".into(), Value::Message(MessageValue::new()));
+    expected.insert("count".into(), Value::I64(-1));
+    assert_eq!(message, expected);
+}
+
+#[test]
+fn count_fields_only_counts_leaves_recursively() {
+    let mut point = MessageValue::new();
+    point.insert("x".into(), Value::F64(1.0));
+    point.insert("y".into(), Value::F64(2.0));
+
+    let mut message = MessageValue::new();
+    message.insert("point".into(), Value::Message(point.clone()));
+    message.insert(
+        "corners".into(),
+        Value::Array(vec![Value::Message(point.clone()), Value::Message(point)]),
+    );
+    message.insert("ids".into(), Value::from(vec![1u32, 2, 3]));
+    message.insert("empty".into(), Value::Array(vec![]));
+    message.insert("header".into(), Value::Message(MessageValue::new()));
+    message.insert("name".into(), Value::String("foo".into()));
+
+    assert_eq!(message.count_fields(), 6);
+    assert_eq!(message.count_all_leaf_fields(), 10);
+    assert_eq!(MessageValue::new().count_fields(), 0);
+    assert_eq!(MessageValue::new().count_all_leaf_fields(), 0);
+}
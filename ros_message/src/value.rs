@@ -1,15 +1,309 @@
-use crate::{Duration, Time};
+use crate::{ConversionError, Duration, Error, Msg, Time};
 use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::hash::Hasher;
 use std::iter::FromIterator;
+use twox_hash::XxHash64;
 
 /// Represents an arbitrary ROS message.
 pub type MessageValue = HashMap<String, Value>;
 
+/// Extension trait with helper methods for handling arbitrary ROS messages.
+pub trait MessageValueExt {
+    /// Replaces every field with the result of calling `f` on the field's name and value.
+    ///
+    /// Only the top level fields are visited, nested messages are passed to `f` as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut message = MessageValue::new();
+    /// message.insert("x".into(), Value::F64(1.0));
+    /// message.insert("name".into(), Value::String("foo".into()));
+    ///
+    /// message.apply(|name, value| match value {
+    ///     Value::F64(v) => Value::F64(v * 2.0),
+    ///     Value::String(v) => Value::String(format!("{}: {}", name, v)),
+    ///     v => v,
+    /// });
+    ///
+    /// assert_eq!(message["x"], Value::F64(2.0));
+    /// assert_eq!(message["name"], Value::String("name: foo".into()));
+    /// ```
+    fn apply<F: Fn(&str, Value) -> Value>(&mut self, f: F);
+
+    /// Replaces every field with the result of calling `f` on the field's name and value,
+    /// descending into nested messages.
+    ///
+    /// Nested messages, including messages inside arrays, are not passed to `f` themselves.
+    /// Instead, their own fields are visited, with `f` receiving the name of the field
+    /// inside the nested message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut point = MessageValue::new();
+    /// point.insert("x".into(), Value::F64(1.0));
+    /// point.insert("y".into(), Value::F64(2.0));
+    ///
+    /// let mut message = MessageValue::new();
+    /// message.insert("point".into(), Value::Message(point));
+    /// message.insert("count".into(), Value::U32(3));
+    ///
+    /// message.apply_recursive(|name, value| match (name, value) {
+    ///     ("x", Value::F64(v)) => Value::F64(-v),
+    ///     (_, v) => v,
+    /// });
+    ///
+    /// let point = message["point"].as_map().unwrap();
+    /// assert_eq!(point["x"], Value::F64(-1.0));
+    /// assert_eq!(point["y"], Value::F64(2.0));
+    /// assert_eq!(message["count"], Value::U32(3));
+    /// ```
+    fn apply_recursive<F: Fn(&str, Value) -> Value>(&mut self, f: F);
+
+    /// Removes the field with the given name, returning its value if it was present.
+    ///
+    /// This allows moving fields into other messages without cloning them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut message = MessageValue::new();
+    /// message.insert("x".into(), Value::F64(1.0));
+    /// message.insert("y".into(), Value::F64(2.0));
+    ///
+    /// let mut other = MessageValue::new();
+    /// other.insert("x".into(), message.drain_field("x").unwrap());
+    ///
+    /// assert_eq!(other["x"], Value::F64(1.0));
+    /// assert!(!message.contains_key("x"));
+    /// assert_eq!(message.drain_field("z"), None);
+    /// ```
+    fn drain_field(&mut self, name: &str) -> Option<Value>;
+
+    /// Hashes the names and types of all fields, ignoring their values.
+    ///
+    /// This is a cheap way of detecting that the message schema has changed, for example to
+    /// invalidate cached data after a package update. Fields are hashed in order of their names,
+    /// since message values don't keep the declaration order.
+    ///
+    /// The element type of an empty array is unknown, so it hashes differently from
+    /// an array with elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut message = MessageValue::new();
+    /// message.insert("x".into(), Value::F64(1.0));
+    /// message.insert("name".into(), Value::String("foo".into()));
+    ///
+    /// let mut other = message.clone();
+    /// other.insert("x".into(), Value::F64(2.0));
+    /// assert_eq!(message.schema_hash(), other.schema_hash());
+    ///
+    /// other.insert("x".into(), Value::F32(2.0));
+    /// assert_ne!(message.schema_hash(), other.schema_hash());
+    /// ```
+    fn schema_hash(&self) -> u64;
+
+    /// Inserts the default value of every field in `schema` that the message is missing.
+    ///
+    /// Fields that are already present are left untouched, even if their type doesn't match
+    /// the schema. Constants are not inserted, since they aren't part of message values.
+    /// Missing nested messages are inserted as empty messages, because the schema doesn't
+    /// contain the fields of its dependencies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Msg, Value};
+    /// # use std::convert::TryInto;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let schema = Msg::new(
+    ///     "geometry_msgs/Point".try_into()?,
+    ///     "float64 x\nfloat64 y\nfloat64 z",
+    /// )?;
+    ///
+    /// let mut message = MessageValue::new();
+    /// message.insert("x".into(), Value::F64(1.0));
+    /// message.apply_schema_defaults(&schema);
+    ///
+    /// assert_eq!(message["x"], Value::F64(1.0));
+    /// assert_eq!(message["y"], Value::F64(0.0));
+    /// assert_eq!(message["z"], Value::F64(0.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn apply_schema_defaults(&mut self, schema: &Msg);
+
+    /// Returns the number of top level fields.
+    ///
+    /// Nested messages and arrays count as a single field each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut point = MessageValue::new();
+    /// point.insert("x".into(), Value::F64(1.0));
+    /// point.insert("y".into(), Value::F64(2.0));
+    ///
+    /// let mut message = MessageValue::new();
+    /// message.insert("point".into(), Value::Message(point));
+    /// message.insert("ids".into(), Value::from(vec![1u32, 2, 3]));
+    ///
+    /// assert_eq!(message.count_fields(), 2);
+    /// ```
+    fn count_fields(&self) -> usize;
+
+    /// Returns the number of leaf values, descending into nested messages and arrays.
+    ///
+    /// Every array element is counted separately, so empty arrays and empty messages
+    /// don't add to the count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, MessageValueExt, Value};
+    /// let mut point = MessageValue::new();
+    /// point.insert("x".into(), Value::F64(1.0));
+    /// point.insert("y".into(), Value::F64(2.0));
+    ///
+    /// let mut message = MessageValue::new();
+    /// message.insert("point".into(), Value::Message(point));
+    /// message.insert("ids".into(), Value::from(vec![1u32, 2, 3]));
+    ///
+    /// assert_eq!(message.count_all_leaf_fields(), 5);
+    /// ```
+    fn count_all_leaf_fields(&self) -> usize;
+}
+
+impl MessageValueExt for MessageValue {
+    fn apply<F: Fn(&str, Value) -> Value>(&mut self, f: F) {
+        for (name, value) in self.iter_mut() {
+            let original = std::mem::replace(value, Value::Bool(false));
+            *value = f(name, original);
+        }
+    }
+
+    fn apply_recursive<F: Fn(&str, Value) -> Value>(&mut self, f: F) {
+        apply_recursive_to_message(self, &f)
+    }
+
+    #[inline]
+    fn drain_field(&mut self, name: &str) -> Option<Value> {
+        self.remove(name)
+    }
+
+    fn schema_hash(&self) -> u64 {
+        // Seeded explicitly, so hashes stay the same across runs and Rust versions.
+        let mut hasher = XxHash64::with_seed(0);
+        hash_message_schema(self, &mut hasher);
+        hasher.finish()
+    }
+
+    fn apply_schema_defaults(&mut self, schema: &Msg) {
+        for field in schema.fields().iter().filter(|field| !field.is_constant()) {
+            if !self.contains_key(field.name()) {
+                self.insert(field.name().into(), field.default_value());
+            }
+        }
+    }
+
+    #[inline]
+    fn count_fields(&self) -> usize {
+        self.len()
+    }
+
+    fn count_all_leaf_fields(&self) -> usize {
+        self.values().map(count_leaf_values).sum()
+    }
+}
+
+fn count_leaf_values(value: &Value) -> usize {
+    match value {
+        Value::Message(message) => message.count_all_leaf_fields(),
+        Value::Array(items) => items.iter().map(count_leaf_values).sum(),
+        _ => 1,
+    }
+}
+
+fn hash_message_schema(message: &MessageValue, hasher: &mut impl Hasher) {
+    hasher.write_usize(message.len());
+    for (name, value) in message.iter().sorted_by(|a, b| Ord::cmp(&a.0, &b.0)) {
+        hasher.write(name.as_bytes());
+        hasher.write_u8(0);
+        hash_value_schema(value, hasher);
+    }
+}
+
+fn hash_value_schema(value: &Value, hasher: &mut impl Hasher) {
+    hasher.write(value.kind().as_bytes());
+    hasher.write_u8(0);
+    match value {
+        Value::Message(message) => hash_message_schema(message, hasher),
+        Value::Array(items) => match items.first() {
+            Some(item) => hash_value_schema(item, hasher),
+            None => hasher.write_u8(0),
+        },
+        _ => {}
+    }
+}
+
+fn apply_recursive_to_message<F: Fn(&str, Value) -> Value>(message: &mut MessageValue, f: &F) {
+    for (name, value) in message.iter_mut() {
+        apply_recursive_to_value(name, value, f);
+    }
+}
+
+fn apply_recursive_to_value<F: Fn(&str, Value) -> Value>(name: &str, value: &mut Value, f: &F) {
+    match value {
+        Value::Message(message) => apply_recursive_to_message(message, f),
+        Value::Array(items) if items.iter().any(|item| matches!(item, Value::Message(_))) => {
+            for item in items {
+                apply_recursive_to_value(name, item, f);
+            }
+        }
+        _ => {
+            let original = std::mem::replace(value, Value::Bool(false));
+            *value = f(name, original);
+        }
+    }
+}
+
+fn flatten_into(output: &mut Vec<(String, Value)>, path: String, value: &Value, separator: &str) {
+    let join = |name: &dyn Display| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}{}{}", path, separator, name)
+        }
+    };
+    match value {
+        Value::Message(message) => {
+            for (name, value) in message {
+                flatten_into(output, join(name), value, separator);
+            }
+        }
+        Value::Array(items) if items.iter().any(|item| matches!(item, Value::Message(_))) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten_into(output, join(&index), item, separator);
+            }
+        }
+        _ => output.push((path, value.clone())),
+    }
+}
+
 /// Represents an arbitrary ROS message or value in it.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Value {
@@ -85,6 +379,60 @@ impl Value {
         }
     }
 
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "bool",
+            Value::I8(_) => "int8",
+            Value::I16(_) => "int16",
+            Value::I32(_) => "int32",
+            Value::I64(_) => "int64",
+            Value::U8(_) => "uint8",
+            Value::U16(_) => "uint16",
+            Value::U32(_) => "uint32",
+            Value::U64(_) => "uint64",
+            Value::F32(_) => "float32",
+            Value::F64(_) => "float64",
+            Value::String(_) => "string",
+            Value::Time(_) => "time",
+            Value::Duration(_) => "duration",
+            Value::Array(_) => "array",
+            Value::Message(_) => "message",
+        }
+    }
+
+    /// Converts the value into any type that can be created from a `Value`.
+    ///
+    /// This is a shorthand for `T::try_from(value)`, which reads better at the end of a chain
+    /// of calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the mismatch if the value doesn't hold the requested type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Value;
+    /// assert_eq!(Value::I32(12).into_typed::<i32>(), Ok(12));
+    /// assert_eq!(
+    ///     Value::String("foo".into()).into_typed::<String>(),
+    ///     Ok("foo".into()),
+    /// );
+    /// assert_eq!(
+    ///     Value::from(vec![1u8, 2, 3]).into_typed::<Vec<u8>>(),
+    ///     Ok(vec![1, 2, 3]),
+    /// );
+    ///
+    /// let error = Value::U32(12).into_typed::<i32>().unwrap_err();
+    /// assert_eq!(error.to_string(), "cannot convert uint32 value into `i32`");
+    /// ```
+    pub fn into_typed<T>(self) -> Result<T, T::Error>
+    where
+        T: TryFrom<Value>,
+    {
+        T::try_from(self)
+    }
+
     /// Returns the content if `Value` is a `bool`.
     ///
     /// # Examples
@@ -387,6 +735,26 @@ impl Value {
         }
     }
 
+    /// Returns the content converted into `T` if `Value` is an array whose elements all convert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Value;
+    /// assert_eq!(
+    ///     Value::Array(vec![1u32.into(), 2u32.into(), 3u32.into()]).as_array_of::<u32>(),
+    ///     Some(vec![1, 2, 3]),
+    /// );
+    /// assert!(Value::Array(vec![1u32.into(), 2u32.into()]).as_array_of::<i32>().is_none());
+    /// assert!(Value::U32(12).as_array_of::<u32>().is_none());
+    /// ```
+    pub fn as_array_of<T: TryFrom<Value>>(&self) -> Option<Vec<T>> {
+        self.as_slice()?
+            .iter()
+            .map(|item| T::try_from(item.clone()).ok())
+            .collect()
+    }
+
     /// Returns a reference to the content if `Value` is a message.
     ///
     /// # Examples
@@ -428,6 +796,209 @@ impl Value {
             None
         }
     }
+
+    /// Checks if the value matches the provided pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{Value, ValuePattern};
+    /// assert!(Value::U8(12).matches(&ValuePattern::AnyNumeric));
+    /// assert!(Value::F32(0.5).matches(&ValuePattern::NumericRange(0.0, 1.0)));
+    /// assert!(!Value::I64(-3).matches(&ValuePattern::NumericRange(0.0, 1.0)));
+    /// assert!(Value::String("foo_bar".into()).matches(&ValuePattern::StringContains("bar".into())));
+    /// assert!(!Value::Bool(true).matches(&ValuePattern::BoolValue(false)));
+    /// ```
+    pub fn matches(&self, pattern: &ValuePattern) -> bool {
+        match pattern {
+            ValuePattern::AnyNumeric => self.as_numeric().is_some(),
+            ValuePattern::BoolValue(expected) => self.as_bool() == Some(*expected),
+            ValuePattern::StringContains(needle) => {
+                matches!(self, Value::String(value) if value.contains(needle.as_str()))
+            }
+            ValuePattern::NumericRange(min, max) => {
+                matches!(self.as_numeric(), Some(value) if *min <= value && value <= *max)
+            }
+            ValuePattern::AnyMessage => matches!(self, Value::Message(_)),
+            ValuePattern::AnyArray => matches!(self, Value::Array(_)),
+        }
+    }
+
+    /// Checks if both values have the same type, ignoring the values themselves.
+    ///
+    /// Message values don't keep track of their message type, so two messages are considered
+    /// to have the same type if they have the same fields, with matching types.
+    /// Arrays have the same type if all of their elements do, with empty arrays matching any array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, Value};
+    /// assert!(Value::U32(12).type_eq(&Value::U32(7)));
+    /// assert!(!Value::U32(12).type_eq(&Value::I32(12)));
+    /// assert!(Value::from(vec![1u8, 2]).type_eq(&Value::from(vec![3u8])));
+    /// assert!(!Value::from(vec![1u8, 2]).type_eq(&Value::from(vec![3i8])));
+    ///
+    /// let mut a = MessageValue::new();
+    /// a.insert("x".into(), Value::F64(1.0));
+    /// let mut b = MessageValue::new();
+    /// b.insert("x".into(), Value::F64(2.0));
+    /// assert!(Value::Message(a.clone()).type_eq(&Value::Message(b)));
+    ///
+    /// let mut c = MessageValue::new();
+    /// c.insert("y".into(), Value::F64(1.0));
+    /// assert!(!Value::Message(a).type_eq(&Value::Message(c)));
+    /// ```
+    pub fn type_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Message(a), Value::Message(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(name, value)| matches!(b.get(name), Some(v) if value.type_eq(v)))
+            }
+            (Value::Array(a), Value::Array(b)) => match a.iter().chain(b).next() {
+                Some(first) => a.iter().chain(b).all(|item| item.type_eq(first)),
+                None => true,
+            },
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    /// Appends `item` to the end of the array.
+    ///
+    /// Fails if `Value` is not an array, or if `item` doesn't have the same type as the existing
+    /// elements, as checked by [`Value::type_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Value;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut value = Value::from(vec![1u8, 2]);
+    /// value.append_to_array(Value::U8(3))?;
+    /// assert_eq!(value, Value::from(vec![1u8, 2, 3]));
+    ///
+    /// assert!(value.append_to_array(Value::I8(4)).is_err());
+    /// assert!(Value::U8(1).append_to_array(Value::U8(2)).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn append_to_array(&mut self, item: Value) -> crate::Result<()> {
+        self.extend_array(std::iter::once(item))
+    }
+
+    /// Appends all `items` to the end of the array.
+    ///
+    /// Fails if `Value` is not an array, or if any of the items doesn't have the same type as the
+    /// existing elements and each other. The array is left unchanged on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::Value;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut value = Value::Array(vec![]);
+    /// value.extend_array(vec![Value::F64(1.0), Value::F64(2.0)])?;
+    /// assert_eq!(value, Value::from(vec![1.0f64, 2.0]));
+    ///
+    /// assert!(value.extend_array(vec![Value::F64(3.0), Value::F32(4.0)]).is_err());
+    /// assert_eq!(value, Value::from(vec![1.0f64, 2.0]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extend_array(&mut self, items: impl IntoIterator<Item = Value>) -> crate::Result<()> {
+        let array = match self {
+            Value::Array(array) => array,
+            _ => return Err(Error::NotAnArray { found: self.kind() }),
+        };
+        let items = items.into_iter().collect::<Vec<_>>();
+        if let Some(first) = array.iter().chain(&items).next() {
+            if let Some(item) = items.iter().find(|item| !item.type_eq(first)) {
+                return Err(Error::ArrayElementMismatch {
+                    expected: first.kind(),
+                    found: item.kind(),
+                });
+            }
+        }
+        array.extend(items);
+        Ok(())
+    }
+
+    /// Flattens nested messages into a list of paths and leaf values.
+    ///
+    /// Every nested message, including messages inside arrays, is replaced by its fields, with the
+    /// path components joined by `separator`. Array elements are addressed by their index.
+    /// The returned pairs are sorted by path.
+    ///
+    /// Values that are not messages are returned as is, with an empty path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ros_message::{MessageValue, Value};
+    /// let mut point = MessageValue::new();
+    /// point.insert("x".into(), Value::F64(1.0));
+    /// point.insert("y".into(), Value::F64(2.0));
+    ///
+    /// let mut message = MessageValue::new();
+    /// message.insert("point".into(), Value::Message(point.clone()));
+    /// message.insert("points".into(), Value::Array(vec![Value::Message(point)]));
+    /// message.insert("data".into(), Value::Array(vec![Value::U8(3)]));
+    ///
+    /// assert_eq!(
+    ///     Value::Message(message).flatten("."),
+    ///     vec![
+    ///         ("data".into(), Value::Array(vec![Value::U8(3)])),
+    ///         ("point.x".into(), Value::F64(1.0)),
+    ///         ("point.y".into(), Value::F64(2.0)),
+    ///         ("points.0.x".into(), Value::F64(1.0)),
+    ///         ("points.0.y".into(), Value::F64(2.0)),
+    ///     ],
+    /// );
+    /// assert_eq!(Value::U8(3).flatten("."), vec![(String::new(), Value::U8(3))]);
+    /// ```
+    pub fn flatten(&self, separator: &str) -> Vec<(String, Value)> {
+        let mut output = vec![];
+        flatten_into(&mut output, String::new(), self, separator);
+        output.sort_by(|(a, _), (b, _)| a.cmp(b));
+        output
+    }
+
+    fn as_numeric(&self) -> Option<f64> {
+        match *self {
+            Value::I8(v) => Some(v.into()),
+            Value::I16(v) => Some(v.into()),
+            Value::I32(v) => Some(v.into()),
+            Value::I64(v) => Some(v as f64),
+            Value::U8(v) => Some(v.into()),
+            Value::U16(v) => Some(v.into()),
+            Value::U32(v) => Some(v.into()),
+            Value::U64(v) => Some(v as f64),
+            Value::F32(v) => Some(v.into()),
+            Value::F64(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Pattern that values can be matched against with `Value::matches`.
+///
+/// Patterns can be deserialized, which allows building message filters from configuration
+/// rather than code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ValuePattern {
+    /// Matches any integer or floating point value.
+    AnyNumeric,
+    /// Matches a `bool` with the given value.
+    BoolValue(bool),
+    /// Matches a `string` that contains the given text.
+    StringContains(String),
+    /// Matches any integer or floating point value within the inclusive range `[min, max]`.
+    NumericRange(f64, f64),
+    /// Matches any embedded message.
+    AnyMessage,
+    /// Matches any array.
+    AnyArray,
 }
 
 impl Display for Value {
@@ -539,147 +1110,186 @@ impl From<HashMap<String, Value>> for Value {
 }
 
 impl TryFrom<Value> for bool {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_bool().ok_or(())
+        value
+            .as_bool()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for i8 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_i8().ok_or(())
+        value
+            .as_i8()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for i16 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_i16().ok_or(())
+        value
+            .as_i16()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for i32 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_i32().ok_or(())
+        value
+            .as_i32()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for i64 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_i64().ok_or(())
+        value
+            .as_i64()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for u8 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_u8().ok_or(())
+        value
+            .as_u8()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for u16 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_u16().ok_or(())
+        value
+            .as_u16()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for u32 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_u32().ok_or(())
+        value
+            .as_u32()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for u64 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_u64().ok_or(())
+        value
+            .as_u64()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for f32 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_f32().ok_or(())
+        value
+            .as_f32()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for f64 {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_f64().ok_or(())
+        value
+            .as_f64()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for String {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.try_into_string().ok_or(())
+        let error = ConversionError::new::<Self>(&value);
+        value.try_into_string().ok_or(error)
     }
 }
 
 impl TryFrom<Value> for Time {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_time().ok_or(())
+        value
+            .as_time()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl TryFrom<Value> for Duration {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.as_duration().ok_or(())
+        value
+            .as_duration()
+            .ok_or_else(|| ConversionError::new::<Self>(&value))
     }
 }
 
 impl<T: TryFrom<Value>> TryFrom<Value> for Vec<T> {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        let value = value.try_into_vec().ok_or(())?;
+        let error = ConversionError::new::<Self>(&value);
+        let value = value.try_into_vec().ok_or(error)?;
         value
             .into_iter()
-            .map(TryInto::try_into)
-            .collect::<Result<Self, _>>()
-            .map_err(|_| ())
+            .map(|item| {
+                let error = ConversionError::new::<T>(&item);
+                item.try_into().map_err(|_| error)
+            })
+            .collect()
     }
 }
 
 impl<T: TryFrom<Value>, const L: usize> TryFrom<Value> for [T; L] {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        let value = value.try_into_vec().ok_or(())?;
+        let error = ConversionError::new::<Self>(&value);
+        let value = value.try_into_vec().ok_or(error)?;
         if value.len() != L {
-            return Err(());
+            return Err(error);
         }
-        array_init::from_iter(value.into_iter().filter_map(|v| v.try_into().ok())).ok_or(())
+        let value = value
+            .into_iter()
+            .map(|item| {
+                let error = ConversionError::new::<T>(&item);
+                item.try_into().map_err(|_| error)
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+        array_init::from_iter(value).ok_or(error)
     }
 }
 
 impl TryFrom<Value> for HashMap<String, Value> {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: Value) -> Result<Self, Self::Error> {
-        value.try_into_map().ok_or(())
+        let error = ConversionError::new::<Self>(&value);
+        value.try_into_map().ok_or(error)
     }
 }
 
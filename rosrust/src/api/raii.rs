@@ -15,7 +15,7 @@ pub struct Publisher<T: Message> {
     clock: Arc<dyn Clock>,
     seq: Arc<AtomicUsize>,
     stream: PublisherStream<T>,
-    _raii: Arc<InteractorRaii<PublisherInfo>>,
+    raii: Arc<InteractorRaii<PublisherInfo>>,
 }
 
 impl<T: Message> Publisher<T> {
@@ -51,10 +51,16 @@ impl<T: Message> Publisher<T> {
             stream,
             clock,
             seq: Arc::new(AtomicUsize::new(0)),
-            _raii: raii,
+            raii,
         })
     }
 
+    // The resolved name, so it includes the namespace if a relative name was used.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        &self.raii.interactor.name
+    }
+
     #[inline]
     pub fn subscriber_count(&self) -> usize {
         self.stream.subscriber_count()
@@ -80,6 +86,12 @@ impl<T: Message> Publisher<T> {
         message.set_header(&self.clock, &self.seq);
         self.stream.send(&message).map_err(Into::into)
     }
+
+    // Skips serialization, so the header's sequence number and timestamp are not filled in.
+    #[inline]
+    pub fn send_raw(&self, message: &[u8]) -> Result<()> {
+        self.stream.send_raw(message).map_err(Into::into)
+    }
 }
 
 struct PublisherInfo {
@@ -189,7 +201,7 @@ impl Service {
     ) -> Result<Self>
     where
         T: ServicePair,
-        F: Fn(T::Request) -> ServiceResult<T::Response> + Send + Sync + 'static,
+        F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
     {
         let api = slave.add_service::<T, F>(hostname, bind_address, name, handler)?;
 
@@ -10,7 +10,7 @@ use crate::api::clock::Delay;
 use crate::api::ShutdownManager;
 use crate::msg::rosgraph_msgs::{Clock as ClockMsg, Log};
 use crate::msg::std_msgs::Header;
-use crate::tcpros::{Client, Message, ServicePair, ServiceResult};
+use crate::tcpros::{probe_service_type, Client, Message, ServicePair, ServiceResult};
 use crate::RawMessageDescription;
 use error_chain::bail;
 use log::error;
@@ -210,6 +210,22 @@ impl Ros {
         Ok(Client::new(&self.name, &uri, &name))
     }
 
+    pub fn service_type(&self, service: &str) -> Result<String> {
+        let name = self.resolver.translate(service)?;
+        let uri = self.master.lookup_service(&name)?;
+        Ok(probe_service_type(&self.name, &uri, &name, None)?)
+    }
+
+    pub fn service_type_with_timeout(
+        &self,
+        service: &str,
+        timeout: std::time::Duration,
+    ) -> Result<String> {
+        let name = self.resolver.translate(service)?;
+        let uri = self.master.lookup_service(&name)?;
+        Ok(probe_service_type(&self.name, &uri, &name, Some(timeout))?)
+    }
+
     pub fn wait_for_service(
         &self,
         service: &str,
@@ -244,6 +260,14 @@ impl Ros {
     where
         T: ServicePair,
         F: Fn(T::Request) -> ServiceResult<T::Response> + Send + Sync + 'static,
+    {
+        self.service_with_ids::<T, _>(service, move |req, _| handler(req))
+    }
+
+    pub fn service_with_ids<T, F>(&self, service: &str, handler: F) -> Result<Service>
+    where
+        T: ServicePair,
+        F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
     {
         let name = self.resolver.translate(service)?;
         Service::new::<T, F>(
@@ -67,7 +67,9 @@ impl SlaveHandler {
             }
         });
 
-        server.register_value("getPid", "PID", |_args| Ok(Value::Int(std::process::id() as i32)));
+        server.register_value("getPid", "PID", |_args| {
+            Ok(Value::Int(std::process::id() as i32))
+        });
 
         let subscriptions = SubscriptionsTracker::default();
         let subs = subscriptions.clone();
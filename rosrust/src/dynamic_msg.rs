@@ -55,6 +55,10 @@ impl DynamicMsg {
         self.dependencies.get(path)
     }
 
+    pub fn dependencies(&self) -> &HashMap<MessagePath, Msg> {
+        &self.dependencies
+    }
+
     pub fn from_headers(headers: HashMap<String, String>) -> Result<Self> {
         let message_type = headers.get("type").chain_err(|| "Missing header `type`")?;
         let message_definition = headers
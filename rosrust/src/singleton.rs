@@ -151,6 +151,16 @@ pub fn client<T: ServicePair>(service: &str) -> Result<Client<T>> {
     ros!().client::<T>(service)
 }
 
+#[inline]
+pub fn service_type(service: &str) -> Result<String> {
+    ros!().service_type(service)
+}
+
+#[inline]
+pub fn service_type_with_timeout(service: &str, timeout: time::Duration) -> Result<String> {
+    ros!().service_type_with_timeout(service, timeout)
+}
+
 #[inline]
 pub fn wait_for_service(service: &str, timeout: Option<time::Duration>) -> Result<()> {
     ros!().wait_for_service(service, timeout)
@@ -165,6 +175,15 @@ where
     ros!().service::<T, F>(service, handler)
 }
 
+#[inline]
+pub fn service_with_ids<T, F>(service: &str, handler: F) -> Result<Service>
+where
+    T: ServicePair,
+    F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
+{
+    ros!().service_with_ids::<T, F>(service, handler)
+}
+
 #[inline]
 pub fn subscribe<T, F>(topic: &str, queue_size: usize, callback: F) -> Result<Subscriber>
 where
@@ -1,4 +1,4 @@
-use super::error::{ErrorKind, Result, ResultExt};
+use super::error::{Error, ErrorKind, Result, ResultExt};
 use super::header::{decode, encode};
 use super::{ServicePair, ServiceResult};
 use crate::rosmsg::RosMsg;
@@ -9,9 +9,10 @@ use socket2::Socket;
 use std::collections::HashMap;
 use std::io;
 use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct ClientResponse<T> {
     handle: thread::JoinHandle<Result<ServiceResult<T>>>,
@@ -46,14 +47,18 @@ pub struct Client<T: ServicePair> {
     phantom: std::marker::PhantomData<T>,
 }
 
-fn connect_to_tcp_with_multiple_attempts(uri: &str, attempts: usize) -> io::Result<TcpStream> {
+fn connect_to_tcp_with_multiple_attempts(
+    uri: &str,
+    attempts: usize,
+    deadline: Option<Instant>,
+) -> io::Result<TcpStream> {
     let mut err = io::Error::new(
         io::ErrorKind::Other,
         "Tried to connect via TCP with 0 connection attempts",
     );
     let mut repeat_delay_ms = 1;
     for _ in 0..attempts {
-        let stream_result = TcpStream::connect(uri).and_then(|stream| {
+        let stream_result = connect_to_tcp(uri, deadline).and_then(|stream| {
             let socket: Socket = stream.into();
             socket.set_linger(None)?;
             let stream: TcpStream = socket.into();
@@ -65,12 +70,78 @@ fn connect_to_tcp_with_multiple_attempts(uri: &str, attempts: usize) -> io::Resu
             }
             Err(error) => err = error,
         }
-        std::thread::sleep(std::time::Duration::from_millis(repeat_delay_ms));
+        let delay = std::time::Duration::from_millis(repeat_delay_ms);
+        // Past the deadline, the timeout is reported instead of the last connection error.
+        let delay = match remaining_until(deadline)? {
+            Some(remaining) => delay.min(remaining),
+            None => delay,
+        };
+        std::thread::sleep(delay);
         repeat_delay_ms *= 2;
     }
     Err(err)
 }
 
+fn connect_to_tcp(uri: &str, deadline: Option<Instant>) -> io::Result<TcpStream> {
+    let remaining = match remaining_until(deadline)? {
+        Some(remaining) => remaining,
+        None => return TcpStream::connect(uri),
+    };
+    let mut err = io::Error::new(
+        io::ErrorKind::Other,
+        format!("No address found for {}", uri),
+    );
+    for address in uri.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, remaining) {
+            Ok(stream) => return Ok(stream),
+            Err(error) => err = error,
+        }
+    }
+    Err(err)
+}
+
+// `None` means there is no deadline, and passing the deadline is reported as a timeout.
+fn remaining_until(deadline: Option<Instant>) -> io::Result<Option<Duration>> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(None),
+    };
+    match deadline.checked_duration_since(Instant::now()) {
+        Some(remaining) if remaining > Duration::from_secs(0) => Ok(Some(remaining)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "Service call deadline exceeded",
+        )),
+    }
+}
+
+// Applies a single deadline to all reads and writes together, so a response that keeps trickling
+// in slowly can't take longer than the timeout in total.
+struct DeadlineStream {
+    stream: TcpStream,
+    deadline: Option<Instant>,
+}
+
+impl Read for DeadlineStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream
+            .set_read_timeout(remaining_until(self.deadline)?)?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for DeadlineStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream
+            .set_write_timeout(remaining_until(self.deadline)?)?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
 impl<T: ServicePair> Client<T> {
     pub fn new(caller_id: &str, uri: &str, service: &str) -> Client<T> {
         Client {
@@ -89,14 +160,55 @@ impl<T: ServicePair> Client<T> {
             &self.info.uri,
             &self.info.caller_id,
             &self.info.service,
+            None,
         )
     }
 
+    // The timeout covers the whole call, from connecting to reading the response, so a service
+    // that stops responding can't block the caller forever. A zero timeout is rejected.
+    pub fn req_with_timeout(
+        &self,
+        args: &T::Request,
+        timeout: Duration,
+    ) -> Result<ServiceResult<T::Response>> {
+        if timeout == Duration::from_secs(0) {
+            bail!("Service call timeout must be greater than zero");
+        }
+        Self::request_body(
+            args,
+            &self.info.uri,
+            &self.info.caller_id,
+            &self.info.service,
+            Some(timeout),
+        )
+        .map_err(|err| {
+            if is_timeout(&err) {
+                ErrorKind::ServiceResponseTimeout.into()
+            } else {
+                err
+            }
+        })
+    }
+
+    // Connects right away, and keeps the connection open until the returned client is dropped.
+    pub fn persistent(&self) -> Result<PersistentClient<T>> {
+        let trimmed_uri = self.info.uri.trim_start_matches("rosrpc://");
+        let mut stream =
+            connect_to_tcp_with_multiple_attempts(trimmed_uri, 15, None).chain_err(|| {
+                ErrorKind::ServiceConnectionFail(self.info.service.clone(), self.info.uri.clone())
+            })?;
+        exchange_headers::<T, _>(&mut stream, &self.info.caller_id, &self.info.service, true)?;
+        Ok(PersistentClient {
+            stream,
+            phantom: std::marker::PhantomData,
+        })
+    }
+
     pub fn req_async(&self, args: T::Request) -> ClientResponse<T::Response> {
         let info = Arc::clone(&self.info);
         ClientResponse {
             handle: thread::spawn(move || {
-                Self::request_body(&args, &info.uri, &info.caller_id, &info.service)
+                Self::request_body(&args, &info.uri, &info.caller_id, &info.service, None)
             }),
         }
     }
@@ -106,65 +218,148 @@ impl<T: ServicePair> Client<T> {
         uri: &str,
         caller_id: &str,
         service: &str,
+        timeout: Option<Duration>,
     ) -> Result<ServiceResult<T::Response>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
         let trimmed_uri = uri.trim_start_matches("rosrpc://");
-        let mut stream = connect_to_tcp_with_multiple_attempts(trimmed_uri, 15)
+        let stream = connect_to_tcp_with_multiple_attempts(trimmed_uri, 15, deadline)
             .chain_err(|| ErrorKind::ServiceConnectionFail(service.into(), uri.into()))?;
+        let mut stream = DeadlineStream { stream, deadline };
 
         // Service request starts by exchanging connection headers
-        exchange_headers::<T, _>(&mut stream, caller_id, service)?;
+        exchange_headers::<T, _>(&mut stream, caller_id, service, false)?;
 
-        let mut writer = io::Cursor::new(Vec::with_capacity(128));
-        // skip the first 4 bytes that will contain the message length
-        writer.set_position(4);
+        write_service_request::<T, _>(&mut stream, args)?;
+        let response = read_service_response::<T, _>(&mut stream)?;
 
-        args.encode(&mut writer)?;
+        let mut dump = vec![];
+        if let Err(err) = stream.read_to_end(&mut dump) {
+            error!("Failed to read from TCP stream: {:?}", err)
+        }
 
-        // write the message length to the start of the header
-        let message_length = (writer.position() - 4) as u32;
-        writer.set_position(0);
-        message_length.encode(&mut writer)?;
+        Ok(response)
+    }
+}
 
-        // Send request to service
-        stream.write_all(&writer.into_inner())?;
+// Sends every request over the same connection, instead of connecting to the service again for
+// each one. The service is asked to keep the connection open through the `persistent` header.
+// A failed request can leave the connection in the middle of a message, so it shouldn't be used
+// for further requests.
+pub struct PersistentClient<T: ServicePair> {
+    stream: TcpStream,
+    phantom: std::marker::PhantomData<T>,
+}
 
-        // Service responds with a boolean byte, signalling success
-        let success = read_verification_byte(&mut stream)
-            .chain_err(|| ErrorKind::ServiceResponseInterruption)?;
-        Ok(if success {
-            // Decode response as response type upon success
+impl<T: ServicePair> PersistentClient<T> {
+    pub fn req(&mut self, args: &T::Request) -> Result<ServiceResult<T::Response>> {
+        write_service_request::<T, _>(&mut self.stream, args)?;
+        read_service_response::<T, _>(&mut self.stream)
+    }
+}
 
-            // TODO: validate response length
-            let _length = stream.read_u32::<LittleEndian>();
+fn write_service_request<T, U>(stream: &mut U, args: &T::Request) -> Result<()>
+where
+    T: ServicePair,
+    U: std::io::Write,
+{
+    let mut writer = io::Cursor::new(Vec::with_capacity(128));
+    // skip the first 4 bytes that will contain the message length
+    writer.set_position(4);
 
-            let data = RosMsg::decode(&mut stream)?;
+    args.encode(&mut writer)?;
 
-            let mut dump = vec![];
-            if let Err(err) = stream.read_to_end(&mut dump) {
-                error!("Failed to read from TCP stream: {:?}", err)
-            }
+    // write the message length to the start of the header
+    let message_length = (writer.position() - 4) as u32;
+    writer.set_position(0);
+    message_length.encode(&mut writer)?;
 
-            Ok(data)
-        } else {
-            // Decode response as string upon failure
-            let data = RosMsg::decode(&mut stream)?;
+    // Send request to service
+    stream.write_all(&writer.into_inner())?;
+    Ok(())
+}
 
-            let mut dump = vec![];
-            if let Err(err) = stream.read_to_end(&mut dump) {
-                error!("Failed to read from TCP stream: {:?}", err)
-            }
+fn read_service_response<T, U>(stream: &mut U) -> Result<ServiceResult<T::Response>>
+where
+    T: ServicePair,
+    U: std::io::Read,
+{
+    // Service responds with a boolean byte, signalling success
+    let success =
+        read_verification_byte(stream).chain_err(|| ErrorKind::ServiceResponseInterruption)?;
+    Ok(if success {
+        // Decode response as response type upon success
 
-            Err(data)
-        })
+        // TODO: validate response length
+        let _length = stream.read_u32::<LittleEndian>();
+
+        Ok(RosMsg::decode(&mut *stream)?)
+    } else {
+        // Decode response as string upon failure
+        Err(RosMsg::decode(&mut *stream)?)
+    })
+}
+
+// Services answer a probe with their connection header, without handling a request.
+// The timeout covers connecting as well as reading the header, since stale registrations are
+// common after a node crashes, and a service may accept the connection but never answer.
+pub fn probe_service_type(
+    caller_id: &str,
+    uri: &str,
+    service: &str,
+    timeout: Option<Duration>,
+) -> Result<String> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let trimmed_uri = uri.trim_start_matches("rosrpc://");
+    let stream = connect_to_tcp_with_multiple_attempts(trimmed_uri, 15, deadline)
+        .chain_err(|| ErrorKind::ServiceConnectionFail(service.into(), uri.into()))?;
+    let mut stream = DeadlineStream { stream, deadline };
+
+    let mut fields = HashMap::<String, String>::new();
+    fields.insert(String::from("callerid"), String::from(caller_id));
+    fields.insert(String::from("service"), String::from(service));
+    fields.insert(String::from("md5sum"), String::from("*"));
+    fields.insert(String::from("probe"), String::from("1"));
+    encode(&mut stream, &fields)?;
+
+    let mut fields = decode(&mut stream)?;
+    match fields.remove("type") {
+        Some(service_type) => Ok(service_type),
+        None => bail!(ErrorKind::HeaderMissingField("type".into())),
     }
 }
 
+fn is_timeout(err: &Error) -> bool {
+    // Depending on the platform, a socket timing out is reported as either of these.
+    let is_io_timeout = |err: &io::Error| {
+        matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut,
+        )
+    };
+    if let ErrorKind::Io(io_err) = err.kind() {
+        return is_io_timeout(io_err);
+    }
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<io::Error>() {
+            return is_io_timeout(io_err);
+        }
+        source = err.source();
+    }
+    false
+}
+
 #[inline]
 fn read_verification_byte<R: std::io::Read>(reader: &mut R) -> std::io::Result<bool> {
     reader.read_u8().map(|v| v != 0)
 }
 
-fn write_request<T, U>(mut stream: &mut U, caller_id: &str, service: &str) -> Result<()>
+fn write_request<T, U>(
+    mut stream: &mut U,
+    caller_id: &str,
+    service: &str,
+    persistent: bool,
+) -> Result<()>
 where
     T: ServicePair,
     U: std::io::Write,
@@ -174,6 +369,9 @@ where
     fields.insert(String::from("service"), String::from(service));
     fields.insert(String::from("md5sum"), T::md5sum());
     fields.insert(String::from("type"), T::msg_type());
+    if persistent {
+        fields.insert(String::from("persistent"), String::from("1"));
+    }
     encode(&mut stream, &fields)?;
     Ok(())
 }
@@ -190,11 +388,16 @@ where
     Ok(())
 }
 
-fn exchange_headers<T, U>(stream: &mut U, caller_id: &str, service: &str) -> Result<()>
+fn exchange_headers<T, U>(
+    stream: &mut U,
+    caller_id: &str,
+    service: &str,
+    persistent: bool,
+) -> Result<()>
 where
     T: ServicePair,
     U: std::io::Write + std::io::Read,
 {
-    write_request::<T, U>(stream, caller_id, service)?;
+    write_request::<T, U>(stream, caller_id, service, persistent)?;
     read_response::<T, U>(stream)
 }
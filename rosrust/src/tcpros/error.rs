@@ -28,6 +28,10 @@ error_chain::error_chain! {
             description("Data stream interrupted while reading service response")
             display("Data stream interrupted while reading service response")
         }
+        ServiceResponseTimeout {
+            description("Service didn't respond in time")
+            display("Service didn't respond in time")
+        }
         ServiceResponseUnknown {
             description("Unknown error caused service response to panic")
             display("Unknown error caused service response to panic")
@@ -1,11 +1,11 @@
-pub use self::client::{Client, ClientResponse};
+pub use self::client::{probe_service_type, Client, ClientResponse, PersistentClient};
 pub use self::error::Error;
 pub use self::publisher::{Publisher, PublisherStream};
 pub use self::service::Service;
 pub use self::subscriber::SubscriberRosConnection;
 
 use crate::rosmsg::RosMsg;
-use crate::Clock;
+use crate::{Clock, Time};
 use std::fmt::Debug;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
@@ -25,6 +25,10 @@ pub trait Message: Clone + Debug + Default + PartialEq + RosMsg + Send + Sync +
     fn md5sum() -> String;
     fn msg_type() -> String;
     fn set_header(&mut self, _clock: &Arc<dyn Clock>, _seq: &Arc<AtomicUsize>) {}
+    // Messages without a `std_msgs/Header` have no stamp.
+    fn header_stamp(&self) -> Option<Time> {
+        None
+    }
 }
 
 pub trait ServicePair: Clone + Debug + Default + PartialEq + Message {
@@ -258,7 +258,22 @@ impl<T: Message> PublisherStream<T> {
     }
 
     pub fn send(&self, message: &T) -> Result<()> {
-        let bytes = Arc::new(message.encode_vec()?);
+        self.send_encoded(message.encode_vec()?);
+        Ok(())
+    }
+
+    // The bytes are the serialized message, without the length prefix.
+    // It's up to the caller to make sure they're actually a valid `T`.
+    pub fn send_raw(&self, message: &[u8]) -> Result<()> {
+        let mut bytes = Vec::with_capacity(message.len() + 4);
+        bytes.extend_from_slice(&(message.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(message);
+        self.send_encoded(bytes);
+        Ok(())
+    }
+
+    fn send_encoded(&self, bytes: Vec<u8>) {
+        let bytes = Arc::new(bytes);
 
         if self.latching {
             *self.last_message.lock().expect(FAILED_TO_LOCK) = Arc::clone(&bytes);
@@ -267,6 +282,5 @@ impl<T: Message> PublisherStream<T> {
         // Subscriptions can only be closed from the Publisher side
         // There is no way for the streamfork thread to fail by itself
         self.stream.send(bytes).expect("Connected thread died");
-        Ok(())
     }
 }
@@ -36,7 +36,7 @@ impl Service {
     ) -> Result<Service>
     where
         T: ServicePair,
-        F: Fn(T::Request) -> ServiceResult<T::Response> + Send + Sync + 'static,
+        F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
     {
         let listener = TcpListener::bind((bind_address, port))?;
         let socket_address = listener.local_addr()?;
@@ -71,14 +71,15 @@ impl Service {
 
 enum RequestType {
     Probe,
-    Action,
+    // Persistent clients send any number of requests over the same connection.
+    Action { caller_id: String, persistent: bool },
 }
 
 fn consume_client<T, U, F>(service: &str, node_name: &str, handler: Arc<F>, mut stream: U)
 where
     T: ServicePair,
     U: std::io::Read + std::io::Write + Send + 'static,
-    F: Fn(T::Request) -> ServiceResult<T::Response> + Send + Sync + 'static,
+    F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
 {
     // Service request starts by exchanging connection headers
     match exchange_headers::<T, _>(&mut stream, service, node_name) {
@@ -92,7 +93,10 @@ where
             }
         }
         // Spawn a thread for handling requests
-        Ok(RequestType::Action) => spawn_request_handler::<T, U, F>(stream, Arc::clone(&handler)),
+        Ok(RequestType::Action {
+            caller_id,
+            persistent,
+        }) => spawn_request_handler::<T, U, F>(stream, caller_id, persistent, Arc::clone(&handler)),
         Ok(RequestType::Probe) => (),
     }
 }
@@ -111,16 +115,21 @@ fn read_request<T: ServicePair, U: std::io::Read>(
     stream: &mut U,
     service: &str,
 ) -> Result<RequestType> {
-    let fields = header::decode(stream)?;
+    let mut fields = header::decode(stream)?;
     header::match_field(&fields, "service", service)?;
-    if fields.get("callerid").is_none() {
-        bail!(ErrorKind::HeaderMissingField("callerid".into()));
-    }
+    let caller_id = match fields.remove("callerid") {
+        Some(caller_id) => caller_id,
+        None => bail!(ErrorKind::HeaderMissingField("callerid".into())),
+    };
     if header::match_field(&fields, "probe", "1").is_ok() {
         return Ok(RequestType::Probe);
     }
     header::match_field(&fields, "md5sum", &T::md5sum())?;
-    Ok(RequestType::Action)
+    let persistent = header::match_field(&fields, "persistent", "1").is_ok();
+    Ok(RequestType::Action {
+        caller_id,
+        persistent,
+    })
 }
 
 fn write_response<T, U>(stream: &mut U, node_name: &str) -> Result<()>
@@ -136,14 +145,19 @@ where
     Ok(())
 }
 
-fn spawn_request_handler<T, U, F>(stream: U, handler: Arc<F>)
+fn spawn_request_handler<T, U, F>(stream: U, caller_id: String, persistent: bool, handler: Arc<F>)
 where
     T: ServicePair,
     U: std::io::Read + std::io::Write + Send + 'static,
-    F: Fn(T::Request) -> ServiceResult<T::Response> + Send + Sync + 'static,
+    F: Fn(T::Request, &str) -> ServiceResult<T::Response> + Send + Sync + 'static,
 {
     thread::spawn(move || {
-        if let Err(err) = handle_request_loop::<T, U, F>(stream, &handler) {
+        let result = if persistent {
+            handle_persistent_requests::<T, U, F>(stream, &caller_id, &handler)
+        } else {
+            handle_request_loop::<T, U, F>(stream, &caller_id, &handler)
+        };
+        if let Err(err) = result {
             if !err.is_closed_connection() {
                 let info = err
                     .iter()
@@ -156,42 +170,19 @@ where
     });
 }
 
-fn handle_request_loop<T, U, F>(mut stream: U, handler: &F) -> Result<()>
+fn handle_request_loop<T, U, F>(mut stream: U, caller_id: &str, handler: &F) -> Result<()>
 where
     T: ServicePair,
     U: std::io::Read + std::io::Write,
-    F: Fn(T::Request) -> ServiceResult<T::Response>,
+    F: Fn(T::Request, &str) -> ServiceResult<T::Response>,
 {
     // Receive request from client
     // TODO: validate message length
     let _length = stream.read_u32::<LittleEndian>();
     // Break out of loop in case of failure to read request
-    // TODO: handle retained connections
     if let Ok(req) = RosMsg::decode(&mut stream) {
         // Call function that handles request and returns response
-        match handler(req) {
-            Ok(res) => {
-                // Send True flag and response in case of success
-                stream.write_u8(1)?;
-                let mut writer = io::Cursor::new(Vec::with_capacity(128));
-                // skip the first 4 bytes that will contain the message length
-                writer.set_position(4);
-
-                res.encode(&mut writer)?;
-
-                // write the message length to the start of the header
-                let message_length = (writer.position() - 4) as u32;
-                writer.set_position(0);
-                message_length.encode(&mut writer)?;
-
-                stream.write_all(&writer.into_inner())?;
-            }
-            Err(message) => {
-                // Send False flag and error message string in case of failure
-                stream.write_u8(0)?;
-                RosMsg::encode(&message, &mut stream)?;
-            }
-        };
+        write_handler_response::<T, U>(&mut stream, handler(req, caller_id))?;
     }
 
     // Upon failure to read request, send client failure message
@@ -200,3 +191,55 @@ where
     encode_str("Failed to parse passed arguments", &mut stream)?;
     Ok(())
 }
+
+// Keeps handling requests until the client closes the connection, without the trailing failure
+// message, since it would be taken for the response to the next request.
+fn handle_persistent_requests<T, U, F>(mut stream: U, caller_id: &str, handler: &F) -> Result<()>
+where
+    T: ServicePair,
+    U: std::io::Read + std::io::Write,
+    F: Fn(T::Request, &str) -> ServiceResult<T::Response>,
+{
+    loop {
+        // TODO: validate message length
+        match stream.read_u32::<LittleEndian>() {
+            Ok(_length) => {}
+            // The client closed the connection between requests.
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err.into()),
+        }
+        let req = RosMsg::decode(&mut stream)?;
+        write_handler_response::<T, U>(&mut stream, handler(req, caller_id))?;
+    }
+}
+
+fn write_handler_response<T, U>(stream: &mut U, response: ServiceResult<T::Response>) -> Result<()>
+where
+    T: ServicePair,
+    U: std::io::Write,
+{
+    match response {
+        Ok(res) => {
+            // Send True flag and response in case of success
+            stream.write_u8(1)?;
+            let mut writer = io::Cursor::new(Vec::with_capacity(128));
+            // skip the first 4 bytes that will contain the message length
+            writer.set_position(4);
+
+            res.encode(&mut writer)?;
+
+            // write the message length to the start of the header
+            let message_length = (writer.position() - 4) as u32;
+            writer.set_position(0);
+            message_length.encode(&mut writer)?;
+
+            stream.write_all(&writer.into_inner())?;
+        }
+        Err(message) => {
+            // Send False flag and error message string in case of failure
+            stream.write_u8(0)?;
+            RosMsg::encode(&message, &mut *stream)?;
+        }
+    };
+    Ok(())
+}
@@ -161,3 +161,68 @@ fn decodes_structures() {
     let data = dynamic_msg.decode(cursor).unwrap();
     assert_eq!(get_message_structure(), data);
 }
+
+fn make_fixed_array_message() -> DynamicMsg {
+    DynamicMsg::new(
+        "custom_msgs/PointPair",
+        r#"geometry_msgs/Point[2] points
+================================================================================
+MSG: geometry_msgs/Point
+float64 x
+float64 y
+float64 z"#,
+    )
+    .unwrap()
+}
+
+fn get_fixed_array_structure() -> MessageValue {
+    let points = (0..2)
+        .map(|i| {
+            let mut point = MessageValue::new();
+            point.insert("x".into(), Value::F64(i as f64 * 3.0 + 1.0));
+            point.insert("y".into(), Value::F64(i as f64 * 3.0 + 2.0));
+            point.insert("z".into(), Value::F64(i as f64 * 3.0 + 3.0));
+            Value::Message(point)
+        })
+        .collect();
+
+    let mut message = MessageValue::new();
+    message.insert("points".into(), Value::Array(points));
+    message
+}
+
+fn get_fixed_array_bytes() -> Vec<u8> {
+    vec![
+        // points, fixed size arrays have no length prefix
+        // points[0]
+        0, 0, 0, 0, 0, 0, 0xf0, 0x3f, // 1.0
+        0, 0, 0, 0, 0, 0, 0x00, 0x40, // 2.0
+        0, 0, 0, 0, 0, 0, 0x08, 0x40, // 3.0
+        // points[1]
+        0, 0, 0, 0, 0, 0, 0x10, 0x40, // 4.0
+        0, 0, 0, 0, 0, 0, 0x14, 0x40, // 5.0
+        0, 0, 0, 0, 0, 0, 0x18, 0x40, // 6.0
+    ]
+}
+
+#[test]
+fn encodes_fixed_arrays_of_remote_types() {
+    let dynamic_msg = make_fixed_array_message();
+    let mut cursor = std::io::Cursor::new(vec![]);
+    dynamic_msg
+        .encode(&get_fixed_array_structure(), &mut cursor)
+        .unwrap();
+    assert_eq!(get_fixed_array_bytes(), cursor.into_inner());
+
+    let mut wrong_length = get_fixed_array_structure();
+    wrong_length.insert("points".into(), Value::Array(vec![]));
+    assert!(dynamic_msg.encode(&wrong_length, std::io::sink()).is_err());
+}
+
+#[test]
+fn decodes_fixed_arrays_of_remote_types() {
+    let dynamic_msg = make_fixed_array_message();
+    let cursor = std::io::Cursor::new(get_fixed_array_bytes());
+    let data = dynamic_msg.decode(cursor).unwrap();
+    assert_eq!(get_fixed_array_structure(), data);
+}
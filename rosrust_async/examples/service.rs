@@ -7,7 +7,7 @@ mod msg {
 }
 
 async fn handle_requests() {
-    while let handle = service.next_request().await {
+    while let Some(handle) = service.next_request().await {
         rosrust::ros_info!("Received a request!");
         tokio::spawn(async move {
             let req = handle.request();
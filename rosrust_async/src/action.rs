@@ -1,15 +1,80 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, Stream};
 use rosrust::error::Result as RosResult;
+use rosrust::RosMsg;
 use rosrust_actionlib::{ self as actionlib, action_server, Action, ActionGoal, ActionResponse };
 use tokio::sync::mpsc;
 use tokio::task;
+use tokio::time;
+
+// How often `goal_stream` checks if the node was shut down while waiting for a goal.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct ActionServer<T: Action> {
     _raii: actionlib::ActionServer<T>,
     rx: mpsc::Receiver<ActionHandle<T>>,
+    stats: Arc<StatsCounters>,
 }
 
 pub struct ActionHandle<T: Action> {
     handle: action_server::ServerSimpleGoalHandle<T>,
+    received: Instant,
+    stats: Arc<StatsCounters>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActionServerStats {
+    pub goals_received: u64,
+    pub goals_completed: u64,
+    pub goals_preempted: u64,
+    pub goals_aborted: u64,
+    // Averaged over every goal that was completed, preempted or aborted. Execution time goes from
+    // receiving the goal until its handle is dropped.
+    pub mean_execution_time_ms: f64,
+}
+
+#[derive(Default)]
+struct StatsCounters {
+    goals_received: AtomicU64,
+    goals_completed: AtomicU64,
+    goals_preempted: AtomicU64,
+    goals_aborted: AtomicU64,
+    total_execution_time_us: AtomicU64,
+}
+
+impl StatsCounters {
+    fn snapshot(&self) -> ActionServerStats {
+        let goals_completed = self.goals_completed.load(Ordering::Relaxed);
+        let goals_preempted = self.goals_preempted.load(Ordering::Relaxed);
+        let goals_aborted = self.goals_aborted.load(Ordering::Relaxed);
+        let finished = goals_completed + goals_preempted + goals_aborted;
+        let mean_execution_time_ms = if finished == 0 {
+            0.0
+        } else {
+            self.total_execution_time_us.load(Ordering::Relaxed) as f64 / finished as f64 / 1000.0
+        };
+
+        ActionServerStats {
+            goals_received: self.goals_received.load(Ordering::Relaxed),
+            goals_completed,
+            goals_preempted,
+            goals_aborted,
+            mean_execution_time_ms,
+        }
+    }
+
+    fn reset(&self) {
+        self.goals_received.store(0, Ordering::Relaxed);
+        self.goals_completed.store(0, Ordering::Relaxed);
+        self.goals_preempted.store(0, Ordering::Relaxed);
+        self.goals_aborted.store(0, Ordering::Relaxed);
+        self.total_execution_time_us.store(0, Ordering::Relaxed);
+    }
 }
 
 impl<T: Action> ActionServer<T> {
@@ -18,18 +83,57 @@ impl<T: Action> ActionServer<T> {
     pub fn new(topic: impl AsRef<str>) -> RosResult<Self> {
         // Why 16 of buffer size? Why not!
         let (tx, rx) = mpsc::channel(16);
+        let stats = Arc::new(StatsCounters::default());
+        let handle_stats = Arc::clone(&stats);
         let _raii: actionlib::ActionServer<T> = actionlib::ActionServer::new_simple(topic.as_ref(), move |handle| {
-            if let Err(_) = tx.blocking_send(ActionHandle { handle }) {
+            handle_stats.goals_received.fetch_add(1, Ordering::Relaxed);
+            let handle = ActionHandle { handle, received: Instant::now(), stats: Arc::clone(&handle_stats) };
+            if let Err(_) = tx.blocking_send(handle) {
                 panic!("unable to send handle");
             }
         })?;
 
-        Ok(ActionServer { _raii, rx })
+        Ok(ActionServer { _raii, rx, stats })
+    }
+
+    pub fn stats(&self) -> ActionServerStats {
+        self.stats.snapshot()
+    }
+
+    // Goals that are still being handled when this is called are counted once they finish.
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
     }
 
     pub async fn recv(&mut self) -> ActionHandle<T> {
         self.rx.recv().await.unwrap()
     }
+
+    // Takes over the server, so it keeps accepting goals for as long as the stream exists. The
+    // stream ends once the node shuts down or the channel is closed.
+    pub fn goal_stream(self) -> impl Stream<Item = ActionHandle<T>> + Unpin {
+        Box::pin(stream::unfold(self, |mut server| async move {
+            // The channel stays open for as long as the server exists, so shutting down is only
+            // noticed by checking in between waits.
+            while rosrust::is_ok() {
+                // `recv` is cancellation safe, so no goal is lost when the timeout hits.
+                if let Ok(handle) = time::timeout(SHUTDOWN_POLL_INTERVAL, server.rx.recv()).await {
+                    return Some((handle?, server));
+                }
+            }
+            None
+        }))
+    }
+
+    // Cancels every goal that was received but not yet handed out by `recv`. Goals that were
+    // already returned by `recv` are left alone, since their handles are owned by the user.
+    pub fn cancel_all_goals(&mut self) {
+        while let Ok(handle) = self.rx.try_recv() {
+            handle.response_builder()
+                .text("This goal has been canceled by the action server")
+                .send_canceled();
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -37,6 +141,14 @@ pub struct PubFeedBackError;
 pub type ResponseBuilder<'a, T> = action_server::ServerGoalHandleMessageBuilder<'a, T>;
 pub type GoalBody<T> = <<T as Action>::Goal as ActionGoal>::Body;
 pub type ActionFeedback<T> = <<T as Action>::Feedback as ActionResponse>::Body;
+pub type ActionResult<T> = <<T as Action>::Result as ActionResponse>::Body;
+
+// Implemented by feedback messages that carry a progress report. Without specialization there is no
+// way to fall back for other feedback types at runtime, so `publish_progress` is only available for
+// actions whose feedback implements this.
+pub trait Progress {
+    fn from_progress(percent: f32, message: &str) -> Self;
+}
 
 impl<T: Action> ActionHandle<T> {
     // TODO: This should probably take ownership of self to ensure it's only going to get called once.
@@ -63,6 +175,13 @@ impl<T: Action> ActionHandle<T> {
         .unwrap()
     }
 
+    pub async fn publish_progress(&self, percent: f32, message: &str) -> Result<(), PubFeedBackError>
+    where
+        ActionFeedback<T>: Progress,
+    {
+        self.publish_feedback(Progress::from_progress(percent, message)).await
+    }
+
     pub fn goal(&self) -> &GoalBody<T> {
         self.handle.goal()
     }
@@ -78,4 +197,294 @@ impl<T: Action> ActionHandle<T> {
     pub fn canceled(&self) -> bool {
         self.handle.canceled()
     }
-}
\ No newline at end of file
+}
+
+// The final state of a goal is only known once its handle is gone, so that's when it's counted.
+impl<T: Action> Drop for ActionHandle<T> {
+    fn drop(&mut self) {
+        let counter = match self.goal_status().state {
+            actionlib::GoalState::Succeeded => &self.stats.goals_completed,
+            actionlib::GoalState::Preempted | actionlib::GoalState::Recalled => &self.stats.goals_preempted,
+            actionlib::GoalState::Aborted => &self.stats.goals_aborted,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        let elapsed = self.received.elapsed().as_micros() as u64;
+        self.stats.total_execution_time_us.fetch_add(elapsed, Ordering::Relaxed);
+    }
+}
+
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
+
+// Keyed on the serialized goal, since the same goal sent again always gets a new goal ID.
+type ResultCache<T> = Arc<Mutex<HashMap<Vec<u8>, (Instant, ActionResult<T>)>>>;
+
+// Remembers the results of succeeded goals for `ttl`. If a goal with the same content is received
+// again in that time, it is answered with the cached result, and never handed out by `recv`. Goals
+// are told apart by their content rather than their ID, since actionlib already ignores a goal
+// whose ID it is still tracking, so a resent goal never reaches the server in the first place.
+pub struct CachingActionServer<T: Action> {
+    inner: ActionServer<T>,
+    cache: ResultCache<T>,
+    ttl: Duration,
+}
+
+impl<T: Action> CachingActionServer<T> {
+    pub fn new(topic: impl AsRef<str>, ttl: Duration) -> RosResult<Self> {
+        Ok(CachingActionServer {
+            inner: ActionServer::new(topic)?,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        })
+    }
+
+    pub async fn recv(&mut self) -> CachingActionHandle<T> {
+        loop {
+            let handle = self.inner.recv().await;
+            // Serializing into memory can't fail.
+            let key = handle.goal().encode_vec().unwrap();
+            let cached = {
+                let mut cache = self.cache.lock().expect(FAILED_TO_LOCK);
+                let ttl = self.ttl;
+                cache.retain(|_, (completed, _)| completed.elapsed() < ttl);
+                cache.get(&key).map(|(_, result)| result.clone())
+            };
+
+            match cached {
+                Some(result) => {
+                    handle.response_builder()
+                        .text("This goal has already been completed")
+                        .result(result)
+                        .send_succeeded();
+                }
+                None => return CachingActionHandle { handle, key, cache: Arc::clone(&self.cache) },
+            }
+        }
+    }
+}
+
+pub struct CachingActionHandle<T: Action> {
+    handle: ActionHandle<T>,
+    key: Vec<u8>,
+    cache: ResultCache<T>,
+}
+
+impl<T: Action> CachingActionHandle<T> {
+    // Only results sent through here are cached. Goals answered through `response_builder` are
+    // handled again the next time they are received.
+    pub fn send_succeeded(self, result: ActionResult<T>) -> bool {
+        self.cache
+            .lock()
+            .expect(FAILED_TO_LOCK)
+            .insert(self.key.clone(), (Instant::now(), result.clone()));
+        self.handle.response_builder().result(result).send_succeeded()
+    }
+}
+
+impl<T: Action> Deref for CachingActionHandle<T> {
+    type Target = ActionHandle<T>;
+
+    fn deref(&self) -> &ActionHandle<T> {
+        &self.handle
+    }
+}
+
+// Limits how often feedback is published for each goal. Feedback published sooner than
+// `1 / max_feedback_hz` after the last published one is dropped, and counted in `dropped_feedback`.
+pub struct ThrottledActionServer<T: Action> {
+    inner: ActionServer<T>,
+    min_interval: Duration,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<T: Action> ThrottledActionServer<T> {
+    // Panics if `max_feedback_hz` isn't positive.
+    pub fn new(topic: impl AsRef<str>, max_feedback_hz: f64) -> RosResult<Self> {
+        Ok(ThrottledActionServer {
+            inner: ActionServer::new(topic)?,
+            min_interval: Duration::from_secs_f64(1.0 / max_feedback_hz),
+            dropped: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    pub async fn recv(&mut self) -> ThrottledActionHandle<T> {
+        ThrottledActionHandle {
+            handle: self.inner.recv().await,
+            min_interval: self.min_interval,
+            last_feedback: Mutex::new(None),
+            dropped: Arc::clone(&self.dropped),
+        }
+    }
+
+    // Counts the feedback dropped for every goal handled by this server.
+    pub fn dropped_feedback(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Action> Deref for ThrottledActionServer<T> {
+    type Target = ActionServer<T>;
+
+    fn deref(&self) -> &ActionServer<T> {
+        &self.inner
+    }
+}
+
+pub struct ThrottledActionHandle<T: Action> {
+    handle: ActionHandle<T>,
+    min_interval: Duration,
+    last_feedback: Mutex<Option<Instant>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<T: Action> ThrottledActionHandle<T> {
+    // Dropped feedback isn't an error, so it returns `Ok`.
+    pub async fn publish_feedback(&self, feedback: ActionFeedback<T>) -> Result<(), PubFeedBackError> {
+        if !self.try_acquire_slot() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+        self.handle.publish_feedback(feedback).await
+    }
+
+    pub async fn publish_progress(&self, percent: f32, message: &str) -> Result<(), PubFeedBackError>
+    where
+        ActionFeedback<T>: Progress,
+    {
+        self.publish_feedback(Progress::from_progress(percent, message)).await
+    }
+
+    fn try_acquire_slot(&self) -> bool {
+        let mut last_feedback = self.last_feedback.lock().expect(FAILED_TO_LOCK);
+        let now = Instant::now();
+        match *last_feedback {
+            Some(last) if now.duration_since(last) < self.min_interval => false,
+            _ => {
+                *last_feedback = Some(now);
+                true
+            }
+        }
+    }
+}
+
+impl<T: Action> Deref for ThrottledActionHandle<T> {
+    type Target = ActionHandle<T>;
+
+    fn deref(&self) -> &ActionHandle<T> {
+        &self.handle
+    }
+}
+
+type SharedActionHandle<T> = Arc<tokio::sync::Mutex<Option<ActionHandle<T>>>>;
+
+// Aborts goals that aren't resolved within `max_execution_time` of being returned by `recv`, so a
+// stuck handler doesn't leave the client waiting forever.
+pub struct BudgetedActionServer<T: Action> {
+    inner: ActionServer<T>,
+    max_execution_time: Duration,
+}
+
+impl<T: Action> BudgetedActionServer<T> {
+    pub fn new(topic: impl AsRef<str>, max_execution_time: Duration) -> RosResult<Self> {
+        Ok(BudgetedActionServer { inner: ActionServer::new(topic)?, max_execution_time })
+    }
+
+    // Must be called from within a Tokio runtime, since the budget of each goal is tracked by its
+    // own task.
+    pub async fn recv(&mut self) -> BudgetedActionHandle<T> {
+        let handle = self.inner.recv().await;
+        let goal = handle.goal().clone();
+        let handle = Arc::new(tokio::sync::Mutex::new(Some(handle)));
+
+        let max_execution_time = self.max_execution_time;
+        let expired = Arc::clone(&handle);
+        tokio::spawn(async move {
+            time::sleep(max_execution_time).await;
+            if let Some(handle) = expired.lock().await.take() {
+                rosrust::ros_warn!(
+                    "Aborting goal {} that exceeded its execution time of {:?}",
+                    handle.goal_id().id,
+                    max_execution_time,
+                );
+                handle.response_builder().text(BUDGET_EXCEEDED).send_aborted();
+            }
+        });
+
+        BudgetedActionHandle { goal, handle }
+    }
+}
+
+impl<T: Action> Deref for BudgetedActionServer<T> {
+    type Target = ActionServer<T>;
+
+    fn deref(&self) -> &ActionServer<T> {
+        &self.inner
+    }
+}
+
+const BUDGET_EXCEEDED: &str = "This goal has exceeded its execution time";
+
+pub struct BudgetedActionHandle<T: Action> {
+    // Kept apart from the handle, so it can be borrowed without locking.
+    goal: GoalBody<T>,
+    // Shared with the budget task, whoever takes it first gets to resolve the goal.
+    handle: SharedActionHandle<T>,
+}
+
+impl<T: Action> BudgetedActionHandle<T> {
+    pub fn goal(&self) -> &GoalBody<T> {
+        &self.goal
+    }
+
+    pub async fn timed_out(&self) -> bool {
+        self.handle.lock().await.is_none()
+    }
+
+    // Whether the client asked to cancel the goal, which should be acknowledged with
+    // `set_preempted`. Always `false` once the goal was aborted for exceeding its budget.
+    pub async fn canceled(&self) -> bool {
+        match &*self.handle.lock().await {
+            Some(handle) => handle.canceled(),
+            None => false,
+        }
+    }
+
+    // Fails once the goal was aborted for exceeding its budget.
+    pub async fn publish_feedback(&self, feedback: ActionFeedback<T>) -> Result<(), PubFeedBackError> {
+        match &*self.handle.lock().await {
+            Some(handle) => handle.publish_feedback(feedback).await,
+            None => Err(PubFeedBackError),
+        }
+    }
+
+    pub async fn publish_progress(&self, percent: f32, message: &str) -> Result<(), PubFeedBackError>
+    where
+        ActionFeedback<T>: Progress,
+    {
+        self.publish_feedback(Progress::from_progress(percent, message)).await
+    }
+
+    // Returns `false` if the goal was already aborted for exceeding its budget.
+    pub async fn set_succeeded(self, result: ActionResult<T>) -> bool {
+        match self.handle.lock().await.take() {
+            Some(handle) => handle.response_builder().result(result).send_succeeded(),
+            None => false,
+        }
+    }
+
+    // Returns `false` if the goal was already aborted for exceeding its budget.
+    pub async fn set_aborted(self, text: &str) -> bool {
+        match self.handle.lock().await.take() {
+            Some(handle) => handle.response_builder().text(text).send_aborted(),
+            None => false,
+        }
+    }
+
+    // Returns `false` if the goal was already aborted for exceeding its budget.
+    pub async fn set_preempted(self, text: &str) -> bool {
+        match self.handle.lock().await.take() {
+            Some(handle) => handle.response_builder().text(text).send_canceled(),
+            None => false,
+        }
+    }
+}
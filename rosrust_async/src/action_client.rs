@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use rosrust::error::Result as RosResult;
+use rosrust_actionlib::action_client::{self, AsyncClientGoalHandle, ClientGoalHandle, State};
+use rosrust_actionlib::{self as actionlib, Action};
+use tokio::sync::{broadcast, watch};
+use tokio::task;
+
+use crate::{ActionFeedback, ActionResult, GoalBody};
+
+// Why 16 of buffer size? Same as the action server.
+const FEEDBACK_CAPACITY: usize = 16;
+
+pub struct ActionClient<T: Action> {
+    inner: Arc<actionlib::ActionClient<T>>,
+}
+
+impl<T: Action> ActionClient<T> {
+    // Only returns once the action server is available, or fails if the node shuts down first.
+    pub async fn new(topic: impl AsRef<str>) -> RosResult<Self> {
+        let topic = topic.as_ref().to_owned();
+        task::spawn_blocking(move || {
+            let inner = actionlib::ActionClient::new(&topic)?;
+            while !inner.wait_for_server(Some(Duration::from_millis(100).into())) {
+                if !rosrust::is_ok() {
+                    return Err("Shutdown".into());
+                }
+            }
+            Ok(ActionClient { inner: Arc::new(inner) })
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn send_goal(&self, goal: GoalBody<T>) -> RosResult<GoalHandle<T>> {
+        if !rosrust::is_ok() {
+            return Err("Shutdown".into());
+        }
+
+        // `None` until the goal is done. Goals that are lost never get a result.
+        let (done_tx, done) = watch::channel(None);
+        let (feedback, _) = broadcast::channel(FEEDBACK_CAPACITY);
+        let feedback_tx = feedback.clone();
+        let inner = Arc::clone(&self.inner);
+        let handle = task::spawn_blocking(move || {
+            inner
+                .build_goal_sender(goal)
+                .on_transition(move |handle| match handle.comm_state() {
+                    State::Done => {
+                        let _ = done_tx.send(Some(handle.result()));
+                    }
+                    State::Lost => {
+                        let _ = done_tx.send(Some(None));
+                    }
+                    _ => {}
+                })
+                .on_feedback(move |_, body| {
+                    // There may be nobody listening for feedback, that's fine.
+                    let _ = feedback_tx.send(body);
+                })
+                .send()
+        })
+        .await
+        .unwrap();
+
+        Ok(GoalHandle { handle, done, feedback })
+    }
+}
+
+type GoalDone<T> = watch::Receiver<Option<Option<ActionResult<T>>>>;
+
+pub struct GoalHandle<T: Action> {
+    handle: AsyncClientGoalHandle<T>,
+    done: GoalDone<T>,
+    feedback: broadcast::Sender<ActionFeedback<T>>,
+}
+
+impl<T: Action> GoalHandle<T> {
+    pub async fn wait_for_result(&mut self) -> RosResult<ActionResult<T>> {
+        loop {
+            if let Some(result) = self.done.borrow().clone() {
+                return result.ok_or_else(|| "Goal finished without a result".into());
+            }
+            if self.done.changed().await.is_err() {
+                return Err("Action client was dropped".into());
+            }
+        }
+    }
+
+    // Only feedback published after this is called is received.
+    pub fn feedback(&self) -> broadcast::Receiver<ActionFeedback<T>> {
+        self.feedback.subscribe()
+    }
+
+    pub fn cancel_goal(&self) {
+        self.handle.cancel();
+    }
+
+    pub fn goal_id(&self) -> actionlib::GoalID {
+        self.handle.goal_id()
+    }
+
+    pub fn goal_state(&self) -> actionlib::GoalState {
+        self.handle.goal_state()
+    }
+
+    pub fn comm_state(&self) -> action_client::State {
+        self.handle.comm_state()
+    }
+}
@@ -1,28 +1,332 @@
+use futures::future;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use tokio::task;
+use tokio::time;
 
-use rosrust::api::error::tcpros::Result as TCPResult;
+use rosrust::api::error::tcpros::{Error as TCPError, ErrorKind as TCPErrorKind, Result as TCPResult};
 use rosrust::error::Result as RosResult;
 use rosrust::ServicePair;
 
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
+// How long `Client::discover` waits for each service to tell its type.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// How a `Client` retries requests that failed to reach the service, e.g. because it restarted.
+// The delay between attempts starts at `initial_delay`, and is multiplied by `backoff_factor` after
+// every attempt, up to `max_delay`. Setting `max_retries` to 0 fails on the first error instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientOptions {
+    max_retries: u32,
+    initial_delay: Duration,
+    backoff_factor: f64,
+    max_delay: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(100),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl ClientOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    // A factor below 1 would shrink the delay between attempts, so it's raised to 1, as is NaN.
+    pub fn backoff_factor(mut self, backoff_factor: f64) -> Self {
+        self.backoff_factor = backoff_factor.max(1.0);
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    // Calls `attempt` until it succeeds or the retries run out, sleeping in between. `attempt` is
+    // given the number of retries so far, so it can tell the first call apart.
+    async fn retry<T, E, F, Fut>(&self, mut attempt: F) -> Result<T, E>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut delay = self.initial_delay.min(self.max_delay);
+        let mut retries = 0;
+        loop {
+            match attempt(retries).await {
+                Err(_) if retries < self.max_retries => {
+                    time::sleep(delay).await;
+                    // Checked before multiplying, since a large factor would overflow the `Duration`.
+                    delay = if delay.as_secs_f64() * self.backoff_factor < self.max_delay.as_secs_f64() {
+                        delay.mul_f64(self.backoff_factor)
+                    } else {
+                        self.max_delay
+                    };
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Client<Srv: ServicePair> {
-    cli: rosrust::Client<Srv>,
+    topic: String,
+    // Replaced when reconnecting, and shared with every clone so they all pick up the new one.
+    cli: Arc<Mutex<rosrust::Client<Srv>>>,
+    options: ClientOptions,
 }
 
 impl<Srv: ServicePair> Client<Srv> {
+    // Fails fast, without retrying requests.
     pub async fn new(topic: impl AsRef<str>) -> RosResult<Self> {
+        Self::with_options(topic, ClientOptions::new().max_retries(0)).await
+    }
+
+    pub async fn with_options(topic: impl AsRef<str>, options: ClientOptions) -> RosResult<Self> {
         crate::wait_until_available(topic.as_ref().to_string()).await?;
         let cli = rosrust::client(topic.as_ref())?;
 
         Ok(Client {
-            cli
+            topic: topic.as_ref().to_string(),
+            cli: Arc::new(Mutex::new(cli)),
+            options,
         })
     }
 
+    fn client(&self) -> rosrust::Client<Srv> {
+        self.cli.lock().expect(FAILED_TO_LOCK).clone()
+    }
+
+    // Only requests that fail before being written to the service (looking it up, or connecting to
+    // it) are retried. Once the request was written the service may have handled it, so any later
+    // failure is returned as is, instead of risking to run a non-idempotent request twice. Errors
+    // returned by the service itself are a valid response.
     pub async fn req(&self, req: Srv::Request) -> TCPResult<Result<Srv::Response, String>> {
-        let cli = self.cli.clone();
-        task::spawn_blocking(move || cli.req(&req))
+        self.req_shared(Arc::new(Mutex::new(req))).await
+    }
+
+    // Requests aren't `Clone`, so every attempt borrows the same one. Attempts never overlap, and
+    // the caller gets the request back once they're done.
+    async fn req_shared(&self, req: Arc<Mutex<Srv::Request>>) -> TCPResult<Result<Srv::Response, String>> {
+        self.options
+            .retry(|retries| {
+                let req = Arc::clone(&req);
+                let topic = self.topic.clone();
+                let cli = Arc::clone(&self.cli);
+                // `Err` means the request never reached the service, and can be tried again.
+                let handle = task::spawn_blocking(move || {
+                    // The service may have moved after restarting, so it's looked up again.
+                    if retries > 0 {
+                        let fresh = rosrust::client(&topic).map_err(|err| TCPError::from(err.to_string()))?;
+                        *cli.lock().expect(FAILED_TO_LOCK) = fresh;
+                    }
+                    let cli = cli.lock().expect(FAILED_TO_LOCK).clone();
+                    let req = req.lock().expect(FAILED_TO_LOCK);
+                    match cli.req(&req) {
+                        Err(err) if matches!(err.kind(), TCPErrorKind::ServiceConnectionFail(..)) => Err(err),
+                        result => Ok(result),
+                    }
+                });
+                async move { handle.await.unwrap() }
+            })
             .await
-            .unwrap()
+            .and_then(|result| result)
+    }
+
+    // Fails with `ServiceResponseTimeout` if the service doesn't respond in time. The blocking call
+    // also gives up once `timeout` has passed, instead of hanging around. A zero timeout fails.
+    pub async fn req_timeout(&self, req: Srv::Request, timeout: Duration) -> TCPResult<Result<Srv::Response, String>> {
+        let cli = self.client();
+        let handle = task::spawn_blocking(move || cli.req_with_timeout(&req, timeout));
+        match time::timeout(timeout, handle).await {
+            Ok(result) => result.unwrap(),
+            Err(_) => Err(TCPErrorKind::ServiceResponseTimeout.into()),
+        }
+    }
+
+    // Returns the names of all services registered with the master that have the given type, e.g.
+    // "std_srvs/Trigger". Every service is asked for its type, since the master doesn't know it. The
+    // services are asked all at once, and each one only gets `PROBE_TIMEOUT` to answer.
+    pub async fn discover(service_type: impl AsRef<str>) -> RosResult<Vec<String>> {
+        let service_type = service_type.as_ref();
+        let state = task::spawn_blocking(rosrust::state).await.unwrap()?;
+        let probes = state.services.into_iter().map(|service| {
+            task::spawn_blocking(move || {
+                let probed = rosrust::service_type_with_timeout(&service.name, PROBE_TIMEOUT);
+                (service.name, probed)
+            })
+        });
+        let services = future::join_all(probes)
+            .await
+            .into_iter()
+            .map(|probe| probe.unwrap())
+            // Services that went away or don't answer the probe in time can't be used anyway.
+            .filter(|(_, probed)| matches!(probed, Ok(t) if t == service_type))
+            .map(|(name, _)| name)
+            .collect();
+        Ok(services)
+    }
+
+    pub async fn first_available(service_type: impl AsRef<str>) -> RosResult<Self> {
+        let service_type = service_type.as_ref();
+        if service_type != Srv::msg_type() {
+            return Err(format!(
+                "Service type {} doesn't match the client's type {}",
+                service_type,
+                Srv::msg_type(),
+            ).into());
+        }
+        match Self::discover(service_type).await?.first() {
+            Some(topic) => Self::new(topic).await,
+            None => Err(format!("No service of type {} is available", service_type).into()),
+        }
+    }
+
+    pub fn with_hooks<H: ClientHooks<Srv>>(self, hooks: H) -> HookedClient<Srv, H> {
+        HookedClient { cli: self, hooks: Arc::new(hooks) }
+    }
+}
+
+// Lets logging, metrics and tracing watch the requests of a client, without cluttering the code
+// that actually makes them.
+pub trait ClientHooks<Srv: ServicePair>: Send + Sync + 'static {
+    fn on_request(&self, req: &Srv::Request);
+
+    // Transport errors are reported as an `Err` as well, and are still returned from `req` as is.
+    fn on_response(&self, req: &Srv::Request, resp: &Result<Srv::Response, String>, latency: Duration);
+}
+
+// Requests are retried like they would be by the wrapped `Client`. The hooks only see the request
+// once, and the latency covers all of its attempts.
+pub struct HookedClient<Srv: ServicePair, H: ClientHooks<Srv>> {
+    cli: Client<Srv>,
+    hooks: Arc<H>,
+}
+
+impl<Srv: ServicePair, H: ClientHooks<Srv>> HookedClient<Srv, H> {
+    pub async fn req(&self, req: Srv::Request) -> TCPResult<Result<Srv::Response, String>> {
+        self.hooks.on_request(&req);
+        let req = Arc::new(Mutex::new(req));
+        let start = Instant::now();
+        let res = self.cli.req_shared(Arc::clone(&req)).await;
+        let latency = start.elapsed();
+        let req = req.lock().expect(FAILED_TO_LOCK);
+        match &res {
+            Ok(resp) => self.hooks.on_response(&req, resp, latency),
+            Err(err) => self.hooks.on_response(&req, &Err(err.to_string()), latency),
+        }
+        res
+    }
+}
+
+impl<Srv: ServicePair, H: ClientHooks<Srv>> Clone for HookedClient<Srv, H> {
+    fn clone(&self) -> Self {
+        HookedClient { cli: self.cli.clone(), hooks: Arc::clone(&self.hooks) }
+    }
+}
+
+// Never has more than `max_concurrent` requests in flight at once, the rest wait for their turn.
+// Every request still opens a connection of its own, only the service lookup is shared.
+#[derive(Clone)]
+pub struct ConcurrencyLimitedClient<Srv: ServicePair> {
+    cli: rosrust::Client<Srv>,
+    permits: Arc<Semaphore>,
+}
+
+impl<Srv: ServicePair> ConcurrencyLimitedClient<Srv> {
+    // Fails if `max_concurrent` is 0, since no request could ever be made.
+    pub async fn new(topic: impl AsRef<str>, max_concurrent: usize) -> RosResult<Self> {
+        if max_concurrent == 0 {
+            return Err("At least one concurrent request has to be allowed".into());
+        }
+        crate::wait_until_available(topic.as_ref().to_string()).await?;
+        let cli = rosrust::client(topic.as_ref())?;
+
+        Ok(ConcurrencyLimitedClient { cli, permits: Arc::new(Semaphore::new(max_concurrent)) })
+    }
+
+    pub async fn req(&self, req: Srv::Request) -> TCPResult<Result<Srv::Response, String>> {
+        // The semaphore is never closed, so acquiring can't fail.
+        let _permit = self.permits.acquire().await.unwrap();
+        let cli = self.cli.clone();
+        task::spawn_blocking(move || cli.req(&req)).await.unwrap()
+    }
+}
+
+// Keeps up to `pool_size` persistent connections to the service open, and never has more than
+// `max_concurrent` requests in flight at once. Every request takes an idle connection from the pool,
+// or opens a new one if there is none, and gives it back once it's done. A connection that fails a
+// request is closed instead, since it may have been left in the middle of a message.
+#[derive(Clone)]
+pub struct PooledClient<Srv: ServicePair> {
+    cli: rosrust::Client<Srv>,
+    idle: Arc<Mutex<Vec<rosrust::PersistentClient<Srv>>>>,
+    pool_size: usize,
+    permits: Arc<Semaphore>,
+}
+
+impl<Srv: ServicePair> PooledClient<Srv> {
+    // Fails if `pool_size` or `max_concurrent` is 0. A `max_concurrent` above `pool_size` is fine,
+    // the extra connections are just closed after their request instead of going back to the pool.
+    pub async fn new(topic: impl AsRef<str>, pool_size: usize, max_concurrent: usize) -> RosResult<Self> {
+        if pool_size == 0 {
+            return Err("At least one connection has to be pooled".into());
+        }
+        if max_concurrent == 0 {
+            return Err("At least one concurrent request has to be allowed".into());
+        }
+        crate::wait_until_available(topic.as_ref().to_string()).await?;
+        let cli = rosrust::client(topic.as_ref())?;
+
+        Ok(PooledClient {
+            cli,
+            idle: Arc::new(Mutex::new(Vec::with_capacity(pool_size))),
+            pool_size,
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+        })
+    }
+
+    pub async fn req(&self, req: Srv::Request) -> TCPResult<Result<Srv::Response, String>> {
+        // The semaphore is never closed, so acquiring can't fail.
+        let _permit = self.permits.acquire().await.unwrap();
+        let cli = self.cli.clone();
+        let idle = Arc::clone(&self.idle);
+        let pool_size = self.pool_size;
+        task::spawn_blocking(move || {
+            let pooled = idle.lock().expect(FAILED_TO_LOCK).pop();
+            let mut conn = match pooled {
+                Some(conn) => conn,
+                None => cli.persistent()?,
+            };
+            let res = conn.req(&req)?;
+            let mut idle = idle.lock().expect(FAILED_TO_LOCK);
+            if idle.len() < pool_size {
+                idle.push(conn);
+            }
+            Ok(res)
+        })
+        .await
+        .unwrap()
     }
 }
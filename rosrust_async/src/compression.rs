@@ -0,0 +1,137 @@
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+use rosrust::error::{Error as RosError, Result as RosResult};
+use rosrust::{Message, RawMessage, RawMessageDescription};
+
+use crate::publisher::{SendQueue, SEND_QUEUE_STOPPED};
+use crate::Publisher;
+
+pub trait Compressor: Send + Sync + 'static {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8>;
+}
+
+#[cfg(feature = "lz4")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LZ4Compressor;
+
+#[cfg(feature = "lz4")]
+impl Compressor for LZ4Compressor {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        lz4_flex::compress_prepend_size(bytes)
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[derive(Clone, Copy, Debug)]
+pub struct ZstdCompressor {
+    pub level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl Default for ZstdCompressor {
+    fn default() -> Self {
+        ZstdCompressor { level: zstd_rs::DEFAULT_COMPRESSION_LEVEL }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Compressor for ZstdCompressor {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        // Compressing into an in-memory buffer can't fail.
+        zstd_rs::encode_all(bytes, self.level).unwrap()
+    }
+}
+
+// The compressed messages are no longer valid `M`s on the wire, so they are published as a
+// `uint8[] data` message containing the compressed serialization of `M`.
+pub struct CompressedPublisher<M: Message> {
+    inner: rosrust::Publisher<RawMessage>,
+    compressor: Arc<dyn Compressor>,
+    queue: Arc<SendQueue>,
+    _message: std::marker::PhantomData<fn(M)>,
+}
+
+impl<M: Message> Clone for CompressedPublisher<M> {
+    fn clone(&self) -> Self {
+        CompressedPublisher {
+            inner: self.inner.clone(),
+            compressor: Arc::clone(&self.compressor),
+            queue: Arc::clone(&self.queue),
+            _message: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<M: Message> Publisher<M> {
+    // Fails if the publisher has clones, since they would keep advertising the topic as `M`.
+    pub async fn with_compression<C: Compressor>(self, compressor: C) -> RosResult<CompressedPublisher<M>> {
+        let description = RawMessageDescription {
+            msg_definition: "uint8[] data\n".into(),
+            md5sum: "*".into(),
+            msg_type: "*".into(),
+        };
+        let Publisher { inner, topic, queue_size, queue, .. } = self;
+        if Arc::strong_count(&queue) > 1 {
+            return Err("Can't compress a publisher that still has clones".into());
+        }
+
+        // Sends that are still queued hold on to the uncompressed publisher, so the topic is only
+        // advertised again once they're done. Dropping the last one stops advertising it as `M`.
+        let (tx, rx) = oneshot::channel();
+        queue.push(move || {
+            drop(inner);
+            let _ = tx.send(rosrust::publish_with_description(&topic, queue_size, description));
+        })?;
+        let inner = rx.await.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))??;
+
+        Ok(CompressedPublisher {
+            inner,
+            compressor: Arc::new(compressor),
+            queue,
+            _message: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<M: Message> CompressedPublisher<M> {
+    #[inline]
+    pub fn set_latching(&mut self, latching: bool) {
+        self.inner.set_latching(latching);
+    }
+
+    #[inline]
+    pub fn set_queue_size(&mut self, queue_size: usize) {
+        self.inner.set_queue_size(queue_size);
+    }
+
+    // Same as `Publisher::send`, the compression also happens on the queue thread.
+    pub async fn send(&mut self, message: M) -> RosResult<()> {
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+        let compressor = Arc::clone(&self.compressor);
+        self.queue.push(move || {
+            let send = || -> RosResult<()> {
+                let mut bytes = Vec::new();
+                message.encode(&mut bytes)?;
+                let compressed = compressor.compress(&bytes);
+
+                let mut data = Vec::with_capacity(compressed.len() + 4);
+                data.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+                data.extend_from_slice(&compressed);
+                inner.send(RawMessage(data))
+            };
+            let _ = tx.send(send());
+        })?;
+        rx.await.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))?
+    }
+}
+
+impl<M: Message> Deref for CompressedPublisher<M> {
+    type Target = rosrust::Publisher<RawMessage>;
+
+    fn deref(&self) -> &rosrust::Publisher<RawMessage> {
+        &self.inner
+    }
+}
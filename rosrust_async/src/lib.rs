@@ -3,14 +3,24 @@ mod publisher;
 mod service;
 mod client;
 mod action;
+mod action_client;
+mod compression;
+pub mod param;
+#[cfg(feature = "opentelemetry")]
+mod tracing_service;
 
 mod oneshot_blocking;
+mod ttl_cache;
 
 pub use subscriber::*;
 pub use publisher::*;
 pub use service::*;
 pub use client::*;
 pub use action::*;
+pub use action_client::*;
+pub use compression::*;
+#[cfg(feature = "opentelemetry")]
+pub use tracing_service::*;
 
 use std::time::Duration;
 use rosrust::error::{Result as RosResult, Error as RosError, ErrorKind as RosErrorKind};
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use std::sync::{ Arc, Mutex, Condvar };
+use std::time::{ Duration, Instant };
 
 #[derive(Debug)]
 pub enum TryRecvError {
@@ -8,6 +9,12 @@ pub enum TryRecvError {
     Closed,
 }
 
+#[derive(Debug)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Closed,
+}
+
 #[derive(Debug)]
 pub struct RecvError;
 
@@ -31,6 +38,30 @@ impl<T> Receiver<T> {
         }
     }
 
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let (mutex, condvar) = &*self.cond_pair;
+        let mut state = mutex.lock().unwrap();
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match state.take() {
+                State::Sent(val) => break Ok(val),
+                State::Closed => {
+                    // Keep the channel closed, so that the `Drop` impls see it.
+                    *state = State::Closed;
+                    break Err(RecvTimeoutError::Closed);
+                },
+                State::NotSent => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break Err(RecvTimeoutError::Timeout);
+                    }
+                    state = condvar.wait_timeout(state, deadline - now).unwrap().0;
+                },
+            }
+        }
+    }
+
     pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
         let (mutex, _) = &*self.cond_pair;
         let mut state = mutex.lock().unwrap();
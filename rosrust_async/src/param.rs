@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use futures::{stream, Stream, StreamExt};
+use rosrust::error::{ErrorKind as RosErrorKind, Result as RosResult};
+use rosrust::Parameter;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{task, time};
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+fn parameter(key: &str) -> RosResult<Parameter> {
+    rosrust::param(key).ok_or_else(|| RosErrorKind::CannotResolveName(key.into()).into())
+}
+
+// `None` if the parameter isn't set.
+pub async fn get<T: DeserializeOwned + Send + 'static>(key: &str) -> RosResult<Option<T>> {
+    let param = parameter(key)?;
+    task::spawn_blocking(move || {
+        if !param.exists()? {
+            return Ok(None);
+        }
+        Ok(Some(param.get::<T>()?))
+    })
+    .await
+    .unwrap()
+}
+
+pub async fn set<T: Serialize + Send + 'static>(key: &str, value: T) -> RosResult<()> {
+    let param = parameter(key)?;
+    task::spawn_blocking(move || Ok(param.set(&value)?)).await.unwrap()
+}
+
+pub async fn delete(key: &str) -> RosResult<()> {
+    let param = parameter(key)?;
+    task::spawn_blocking(move || Ok(param.delete()?)).await.unwrap()
+}
+
+// Emits the current value, and then every new value the parameter is set to. Nothing is emitted
+// while the parameter isn't set. The stream ends once the node shuts down.
+pub async fn watch<T>(key: &str) -> RosResult<impl Stream<Item = T> + Send>
+where
+    T: DeserializeOwned + PartialEq + Clone + Send + 'static,
+{
+    watch_with_interval(key, WATCH_POLL_INTERVAL).await
+}
+
+pub async fn watch_with_interval<T>(key: &str, poll_interval: Duration) -> RosResult<impl Stream<Item = T> + Send>
+where
+    T: DeserializeOwned + PartialEq + Clone + Send + 'static,
+{
+    // Fetched up front, so problems like an unreachable master are reported right away.
+    let initial = get::<T>(key).await?;
+    let state = (key.to_owned(), time::interval(poll_interval), initial.clone());
+    let changes = stream::unfold(state, |(key, mut interval, mut last)| async move {
+        while rosrust::is_ok() {
+            interval.tick().await;
+            // Failing to fetch the parameter is treated as no change, the next poll tries again.
+            if let Ok(Some(value)) = get::<T>(&key).await {
+                if last.as_ref() != Some(&value) {
+                    last = Some(value.clone());
+                    return Some((value, (key, interval, last)));
+                }
+            }
+        }
+        None
+    });
+    Ok(stream::iter(initial).chain(changes))
+}
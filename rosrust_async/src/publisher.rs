@@ -1,18 +1,93 @@
+use bytes::Bytes;
+use futures::{ready, Sink};
+use std::collections::VecDeque;
+use std::future::Future;
 use std::ops::Deref;
-use tokio::task;
+use std::pin::Pin;
+use std::sync::{mpsc as std_mpsc, Arc};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::{self, Instant};
 
-use rosrust::error::Result as RosResult;
-use rosrust::Message;
+use rosrust::error::{Error as RosError, ErrorKind as RosErrorKind, Result as RosResult};
+use rosrust::{DynamicMsg, Message, RawMessageDescription};
+use serde::Serialize;
+use rosrust_msg::std_msgs::Header;
+
+// Implemented by messages with a `std_msgs/Header`, so it can be filled in before sending.
+pub trait HasHeader {
+    fn header_mut(&mut self) -> &mut Header;
+}
+
+pub(crate) static SEND_QUEUE_STOPPED: &str = "Publisher send queue stopped";
+const SUBSCRIBER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+type SendJob = Box<dyn FnOnce() + Send>;
+
+// Sends of a publisher (and all of its clones) are handed over to a single thread, which runs them
+// in order. Handing a send over never blocks, so it's done as soon as the send future is polled.
+// After that, dropping the future only stops waiting for the result, the message still goes out
+// exactly once and in order.
+pub(crate) struct SendQueue {
+    tx: std_mpsc::Sender<SendJob>,
+}
+
+impl SendQueue {
+    fn new() -> SendQueue {
+        let (tx, rx) = std_mpsc::channel::<SendJob>();
+        // The thread stops once every clone of the publisher is gone.
+        thread::spawn(move || {
+            for job in rx {
+                job();
+            }
+        });
+        SendQueue { tx }
+    }
+
+    pub(crate) fn push(&self, job: impl FnOnce() + Send + 'static) -> RosResult<()> {
+        self.tx.send(Box::new(job)).map_err(|_| SEND_QUEUE_STOPPED.into())
+    }
+}
 
 #[derive(Clone)]
 pub struct Publisher<M: Message> {
-    inner: rosrust::Publisher<M>,
+    pub(crate) inner: rosrust::Publisher<M>,
+    pub(crate) topic: String,
+    pub(crate) queue_size: usize,
+    // What the topic was advertised with.
+    description: Arc<RawMessageDescription>,
+    // Shared by every clone, so it also tells whether there are any.
+    pub(crate) queue: Arc<SendQueue>,
 }
 
 impl<M: Message> Publisher<M> {
     pub fn new(topic: impl AsRef<str>, queue_size: usize) -> RosResult<Publisher<M>> {
-        let inner = rosrust::publish(topic.as_ref(), queue_size)?;
-        Ok(Publisher{ inner })
+        let description = RawMessageDescription::from_message::<M>();
+        let inner = rosrust::publish_with_description(topic.as_ref(), queue_size, description.clone())?;
+        Ok(Publisher {
+            inner,
+            topic: topic.as_ref().to_owned(),
+            queue_size,
+            description: Arc::new(description),
+            queue: Arc::new(SendQueue::new()),
+        })
+    }
+
+    fn queue_send(&self, message: M) -> RosResult<oneshot::Receiver<RosResult<()>>> {
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+        self.queue.push(move || {
+            let _ = tx.send(inner.send(message));
+        })?;
+        Ok(rx)
+    }
+
+    // Same as the underlying publisher, the name is resolved, unlike the one passed to `new`.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        self.inner.topic()
     }
 
     #[inline]
@@ -23,14 +98,91 @@ impl<M: Message> Publisher<M> {
     #[inline]
     pub fn set_queue_size(&mut self, queue_size: usize) {
         self.inner.set_queue_size(queue_size);
+        self.queue_size = queue_size;
     }
 
-    // I don't think this future is cancellable as is.
-    // NOTE: Don't use in select.
+    // Cancellation safe, so it can be used in `select!`. If the future is dropped before it was
+    // polled, nothing is sent. Otherwise the message is sent, even if the future is dropped later.
     pub async fn send(&mut self, message: M) -> RosResult<()> {
-        let self_clone = self.clone();
-        let handle = task::spawn_blocking(move || self_clone.inner.send(message));
-        handle.await.unwrap()
+        let rx = self.queue_send(message)?;
+        rx.await.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))?
+    }
+
+    // Sends bytes that were already serialized (e.g. received from another topic), without the length
+    // prefix. Nothing checks them against `M`, if they don't match its definition the subscribers
+    // will fail to decode the message. The header is also sent as is, instead of being filled in.
+    pub async fn send_raw_bytes(&mut self, bytes: Bytes) -> RosResult<()> {
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+        self.queue.push(move || {
+            let _ = tx.send(inner.send_raw(&bytes));
+        })?;
+        rx.await.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))?
+    }
+
+    // Doesn't wait for the message to be sent, so it can be called outside of async code.
+    // `on_sent` is only called if sending succeeded.
+    pub fn send_with_confirmation(&self, message: M, on_sent: impl FnOnce() + Send + 'static) -> RosResult<()> {
+        let inner = self.inner.clone();
+        self.queue.push(move || {
+            if inner.send(message).is_ok() {
+                on_sent();
+            }
+        })
+    }
+
+    // Messages still waiting in the queue when the deadline is reached are dropped instead of being
+    // sent late. A send that already started by then can't be interrupted, so it may still finish
+    // after the deadline, but the caller gets a timeout either way.
+    pub async fn send_with_deadline(&mut self, message: M, deadline: Instant) -> RosResult<()> {
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+        let expires = deadline.into_std();
+        self.queue.push(move || {
+            let result = if std::time::Instant::now() >= expires {
+                Err(RosErrorKind::TimeoutError.into())
+            } else {
+                inner.send(message)
+            };
+            let _ = tx.send(result);
+        })?;
+        match time::timeout_at(deadline, rx).await {
+            Ok(result) => result.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))?,
+            Err(_) => Err(RosErrorKind::TimeoutError.into()),
+        }
+    }
+
+    // Useful right after creating the publisher, since messages sent before anyone subscribed are
+    // lost. Fails if the node shuts down first.
+    pub async fn wait_for_subscribers(&self, count: usize) -> RosResult<()> {
+        self.wait_for_subscribers_with_interval(count, SUBSCRIBER_POLL_INTERVAL).await
+    }
+
+    // Fails right away if `poll_interval` is zero.
+    pub async fn wait_for_subscribers_with_interval(&self, count: usize, poll_interval: Duration) -> RosResult<()> {
+        if poll_interval == Duration::from_secs(0) {
+            return Err("The subscriber poll interval must be greater than zero".into());
+        }
+        let mut interval = time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            if self.inner.subscriber_count() >= count {
+                return Ok(());
+            }
+            if !rosrust::is_ok() {
+                return Err("Shutdown".into());
+            }
+        }
+    }
+
+    pub async fn send_with_header(&mut self, mut message: M, frame_id: &str) -> RosResult<()>
+    where
+        M: HasHeader,
+    {
+        let header = message.header_mut();
+        header.stamp = rosrust::now();
+        header.frame_id = frame_id.to_string();
+        self.send(message).await
     }
 }
 
@@ -40,4 +192,142 @@ impl<M: Message> Deref for Publisher<M> {
     fn deref(&self) -> &rosrust::Publisher<M> {
         &self.inner
     }
-}
\ No newline at end of file
+}
+
+// Sends go through the same queue as `Publisher::send`, so they're handed over right away and
+// flushing waits for all of them to be sent.
+pub struct PublisherSink<M: Message> {
+    inner: Publisher<M>,
+    pending: VecDeque<oneshot::Receiver<RosResult<()>>>,
+}
+
+// Nothing in here is ever pinned, the sink is only behind `Pin` because of the trait.
+impl<M: Message> Unpin for PublisherSink<M> {}
+
+impl<M: Message> Publisher<M> {
+    pub fn into_sink(self) -> PublisherSink<M> {
+        PublisherSink { inner: self, pending: VecDeque::new() }
+    }
+}
+
+impl<M: Message> Sink<M> for PublisherSink<M> {
+    type Error = RosError;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<RosResult<()>> {
+        if rosrust::is_ok() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Ready(Err("Shutdown".into()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, message: M) -> RosResult<()> {
+        let this = self.get_mut();
+        let rx = this.inner.queue_send(message)?;
+        this.pending.push_back(rx);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<RosResult<()>> {
+        let this = self.get_mut();
+        while let Some(rx) = this.pending.front_mut() {
+            let result = ready!(Pin::new(rx).poll(cx));
+            this.pending.pop_front();
+            result.map_err(|_| RosError::from(SEND_QUEUE_STOPPED))??;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<RosResult<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<M: Message> Deref for PublisherSink<M> {
+    type Target = rosrust::Publisher<M>;
+
+    fn deref(&self) -> &rosrust::Publisher<M> {
+        &self.inner
+    }
+}
+
+#[derive(Debug)]
+pub struct SchemaError(pub String);
+
+#[derive(Debug)]
+pub enum ValidatedSendError {
+    Schema(SchemaError),
+    Ros(RosError),
+}
+
+// Checks every message against the definition the topic was advertised with before sending it,
+// so that malformed messages are caught here instead of failing to decode on the subscriber's
+// side. Messages are checked through their `Serialize` implementation, which catches fields of the
+// wrong type or out of range, arrays of the wrong length, and fields missing from the definition.
+// Fields missing from the message are taken to have their default value.
+#[derive(Clone)]
+pub struct ValidatingPublisher<M: Message> {
+    inner: Publisher<M>,
+    schema: Arc<DynamicMsg>,
+}
+
+impl<M: Message> Publisher<M> {
+    // Fails if the definition the topic was advertised with can't be parsed.
+    pub fn with_schema_validation(self) -> RosResult<ValidatingPublisher<M>> {
+        let schema = DynamicMsg::new(&self.description.msg_type, &self.description.msg_definition)?;
+        Ok(ValidatingPublisher { inner: self, schema: Arc::new(schema) })
+    }
+}
+
+impl<M: Message + Serialize> ValidatingPublisher<M> {
+    pub fn validate(&self, message: &M) -> Result<(), SchemaError> {
+        let json = serde_json::to_value(message).map_err(|err| SchemaError(err.to_string()))?;
+        ros_message::from_json(json, self.schema.msg(), self.schema.dependencies())
+            .map(|_| ())
+            .map_err(|err| SchemaError(err.to_string()))
+    }
+
+    pub async fn send(&mut self, message: M) -> Result<(), ValidatedSendError> {
+        self.validate(&message).map_err(ValidatedSendError::Schema)?;
+        self.inner.send(message).await.map_err(ValidatedSendError::Ros)
+    }
+}
+
+impl<M: Message> Deref for ValidatingPublisher<M> {
+    type Target = rosrust::Publisher<M>;
+
+    fn deref(&self) -> &rosrust::Publisher<M> {
+        &self.inner
+    }
+}
+
+// Serializes and publishes on the calling task instead of handing the send over to the queue
+// thread. This blocks the async executor for as long as the send takes, so it's only worth it
+// for small messages where the hand-off costs more than the send itself.
+// Sends that were already queued on the original publisher may still go out after inline ones.
+#[derive(Clone)]
+pub struct InlinePublisher<M: Message> {
+    inner: Publisher<M>,
+}
+
+impl<M: Message> Publisher<M> {
+    pub fn with_inline_send(self) -> InlinePublisher<M> {
+        InlinePublisher { inner: self }
+    }
+}
+
+impl<M: Message> InlinePublisher<M> {
+    // Like any `async fn`, nothing happens until the future is first polled. The whole send then
+    // happens in that first poll, so it either doesn't start, or is done before the poll returns.
+    pub async fn send(&mut self, message: M) -> RosResult<()> {
+        self.inner.inner.send(message)
+    }
+}
+
+impl<M: Message> Deref for InlinePublisher<M> {
+    type Target = rosrust::Publisher<M>;
+
+    fn deref(&self) -> &rosrust::Publisher<M> {
+        &self.inner
+    }
+}
@@ -1,39 +1,222 @@
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::Stream;
+
 use rosrust::error::Result as RosResult;
 use rosrust::ServicePair;
+use rosrust_msg::std_srvs::{Trigger, TriggerRes};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{self, error::Elapsed};
+use tokio_util::sync::CancellationToken;
+
+use crate::oneshot_blocking::{self as oneshot, RecvTimeoutError};
+use crate::ttl_cache::TtlCache;
 
-use crate::oneshot_blocking as oneshot;
+// How often a request waiting for its response, or `next_request` waiting for a request, checks if
+// the service was shut down.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const SERVICE_UNAVAILABLE: &str = "Service is shutting down";
+const REQUEST_DROPPED: &str = "Request was dropped without a response";
+const REQUEST_TIMED_OUT: &str = "Request timed out without a response";
+const NOT_AUTHORIZED: &str = "Caller is not authorized to call this service";
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
+
+type CallerFilter = Arc<dyn Fn(&str) -> bool + Send + Sync>;
 
 pub struct Service<S: ServicePair> {
     raii: rosrust::Service,
     // This should really be a Single Producer, Single Consumer. But there is no such
     // channel in the Tokio crate.
     rx: mpsc::Receiver<RequestHandle<S>>,
+    shutdown: CancellationToken,
+    topic: String,
+    stats: Arc<ServiceStats>,
+    caller_filter: Arc<Mutex<Option<CallerFilter>>>,
+    // Requests that were received, but are still being handed over to `rx`.
+    arriving: Arc<AtomicUsize>,
 }
 
 impl<S: ServicePair> Service<S> {
     pub fn new(topic: impl AsRef<str>) -> RosResult<Service<S>> {
         let (tx, rx) = mpsc::channel(1);
+        let shutdown = CancellationToken::new();
+        let shutdown_clone = shutdown.clone();
+        let stats = Arc::new(ServiceStats::default());
+        let stats_clone = Arc::clone(&stats);
+        let caller_filter = Arc::new(Mutex::new(None::<CallerFilter>));
+        let caller_filter_clone = Arc::clone(&caller_filter);
+        let arriving = Arc::new(AtomicUsize::new(0));
+        let arriving_clone = Arc::clone(&arriving);
 
-        let raii = rosrust::service::<S, _>(
+        let raii = rosrust::service_with_ids::<S, _>(
             topic.as_ref(),
-            move |req: S::Request| -> Result<S::Response, String> {
-                let (response, handle) = RequestHandle::new_pair(req);
-                tx.blocking_send(handle).unwrap();
-
-                match response.recv() {
-                    Ok(resp) => return resp,
-                    Err(_) => panic!("Handle was dropped before responding"),
-                }
+            move |req: S::Request, caller_id: &str| -> Result<S::Response, String> {
+                let start = Instant::now();
+                // Clone the filter, so the lock isn't held while calling it.
+                let filter = caller_filter_clone.lock().expect(FAILED_TO_LOCK).clone();
+                let response = match filter {
+                    Some(filter) if !filter(caller_id) => Err(NOT_AUTHORIZED.into()),
+                    _ => handle_request(req, &tx, &shutdown_clone, &arriving_clone),
+                };
+                stats_clone.record(response.is_ok(), start.elapsed());
+                response
             },
         )?;
 
-        Ok(Service { raii, rx })
+        Ok(Service { raii, rx, shutdown, topic: topic.as_ref().to_string(), stats, caller_filter, arriving })
     }
 
-    #[inline]
-    pub async fn next_request(&mut self) -> RequestHandle<S> {
-        self.rx.recv().await.unwrap()
+    // Closes the service to new callers, like dropping it, but requests that already arrived can
+    // still be taken from the returned stream and answered. The stream ends once there are none left.
+    pub fn close(mut self) -> PendingRequests<S> {
+        // `Service` implements `Drop`, so the fields are swapped out instead of moved. The original
+        // shutdown token is never cancelled, since that would fail the requests still pending.
+        let (_, closed_rx) = mpsc::channel(1);
+        let rx = std::mem::replace(&mut self.rx, closed_rx);
+        let _ = std::mem::replace(&mut self.shutdown, CancellationToken::new());
+        PendingRequests { rx, arriving: Arc::clone(&self.arriving) }
+    }
+
+    // Only requests from nodes for which `f` returns `true` reach `next_request`, the rest get a
+    // "not authorized" error. `f` is given the caller's node name, and replaces any previous filter.
+    pub fn with_caller_filter<F: Fn(&str) -> bool + Send + Sync + 'static>(self, f: F) -> Self {
+        *self.caller_filter.lock().expect(FAILED_TO_LOCK) = Some(Arc::new(f));
+        self
+    }
+
+    // Returns `None` once the node is shutting down, or the service is gone.
+    pub async fn next_request(&mut self) -> Option<RequestHandle<S>> {
+        while rosrust::is_ok() {
+            // `recv` is cancellation safe, so no request is lost when the timeout hits.
+            if let Ok(handle) = time::timeout(SHUTDOWN_POLL_INTERVAL, self.rx.recv()).await {
+                return handle;
+            }
+        }
+        None
+    }
+
+    // `Ok(None)` means the service is shutting down, same as `next_request`, while `Err` means no
+    // request arrived in time. No request is lost when timing out.
+    pub async fn next_request_timeout(&mut self, timeout: Duration) -> Result<Option<RequestHandle<S>>, Elapsed> {
+        time::timeout(timeout, self.next_request()).await
+    }
+
+    pub fn with_request_timeout(self, timeout: Duration) -> TimeoutService<S> {
+        TimeoutService { inner: self, timeout }
+    }
+
+    // Serves `{topic}/_health` until the returned endpoint is dropped, answering with statistics of
+    // the requests handled by this service so far. Must be called from within a Tokio runtime.
+    pub fn health_endpoint(&self) -> RosResult<ServiceHealthEndpoint> {
+        let mut health = Service::<Trigger>::new(format!("{}/_health", self.topic))?;
+        let stats = Arc::clone(&self.stats);
+        let task = tokio::spawn(async move {
+            while let Some(handle) = health.next_request().await {
+                handle.send_ok(TriggerRes { success: true, message: stats.summary() });
+            }
+        });
+
+        Ok(ServiceHealthEndpoint { task })
+    }
+}
+
+fn handle_request<S: ServicePair>(
+    req: S::Request,
+    tx: &mpsc::Sender<RequestHandle<S>>,
+    shutdown: &CancellationToken,
+    arriving: &AtomicUsize,
+) -> Result<S::Response, String> {
+    if shutdown.is_cancelled() {
+        return Err(SERVICE_UNAVAILABLE.into());
+    }
+
+    let (mut response, handle) = RequestHandle::new_pair(req);
+    arriving.fetch_add(1, Ordering::SeqCst);
+    let sent = tx.blocking_send(handle);
+    arriving.fetch_sub(1, Ordering::SeqCst);
+    if sent.is_err() {
+        return Err(SERVICE_UNAVAILABLE.into());
+    }
+
+    loop {
+        match response.recv_timeout(SHUTDOWN_POLL_INTERVAL) {
+            Ok(resp) => return resp,
+            Err(RecvTimeoutError::Timeout) if !shutdown.is_cancelled() => (),
+            // The service is gone, and nobody is going to respond anymore. Requests that were
+            // still waiting in the channel are dropped along with it.
+            Err(_) if shutdown.is_cancelled() => return Err(SERVICE_UNAVAILABLE.into()),
+            Err(_) => return Err(REQUEST_DROPPED.into()),
+        }
+    }
+}
+
+pub struct PendingRequests<S: ServicePair> {
+    rx: mpsc::Receiver<RequestHandle<S>>,
+    arriving: Arc<AtomicUsize>,
+}
+
+impl<S: ServicePair> Stream for PendingRequests<S> {
+    type Item = RequestHandle<S>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<RequestHandle<S>>> {
+        let this = self.get_mut();
+        match this.rx.poll_recv(cx) {
+            Poll::Pending if this.arriving.load(Ordering::SeqCst) == 0 => Poll::Ready(None),
+            // Anything still arriving wakes us up once it's in the channel.
+            poll => poll,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ServiceStats {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    total_latency_us: AtomicU64,
+}
+
+impl ServiceStats {
+    fn record(&self, ok: bool, latency: Duration) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if !ok {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_latency_us.fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn summary(&self) -> String {
+        let requests = self.requests.load(Ordering::Relaxed);
+        let (error_rate, mean_latency_ms) = if requests == 0 {
+            (0.0, 0.0)
+        } else {
+            let errors = self.errors.load(Ordering::Relaxed);
+            let total_latency_us = self.total_latency_us.load(Ordering::Relaxed);
+            (errors as f64 / requests as f64, total_latency_us as f64 / requests as f64 / 1000.0)
+        };
+        format!("requests: {}, error rate: {:.3}, mean latency: {:.3} ms", requests, error_rate, mean_latency_ms)
+    }
+}
+
+pub struct ServiceHealthEndpoint {
+    task: JoinHandle<()>,
+}
+
+impl Drop for ServiceHealthEndpoint {
+    fn drop(&mut self) {
+        // Dropping the task drops the health service along with it.
+        self.task.abort();
+    }
+}
+
+impl<S: ServicePair> Drop for Service<S> {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
     }
 }
 
@@ -45,6 +228,8 @@ impl<S: ServicePair> std::ops::Deref for Service<S> {
     }
 }
 
+// Dropping the handle without responding (e.g. because the task handling it panicked) still gets
+// the caller an error response, saying the request was dropped.
 pub struct RequestHandle<S: ServicePair> {
     request: S::Request,
     tx: oneshot::Sender<Result<S::Response, String>>,
@@ -65,16 +250,15 @@ impl<S: ServicePair> RequestHandle<S> {
     // NOTE: The ideal implementation is for this function to return some kind of
     //       result indicating if the response was send successfully. But in the
     //       current wrapper design, I don't think that's possible.
+    //
+    // If the service was shut down in the meantime, the caller already got an error and the
+    // response is discarded.
     pub fn send_ok(self, response: S::Response) {
-        if let Err(_) = self.tx.send(Ok(response)) {
-            panic!("failed to send value");
-        }
+        let _ = self.tx.send(Ok(response));
     }
 
     pub async fn send_err(self, msg: impl Into<String>) {
-        if let Err(_) = self.tx.send(Err(msg.into())) {
-            panic!("failed to send value");
-        }
+        let _ = self.tx.send(Err(msg.into()));
     }
 }
 
@@ -83,3 +267,303 @@ impl<S: ServicePair> std::fmt::Debug for RequestHandle<S> {
         write!(f, "RequestHandle {{..}}")
     }
 }
+
+// Same as `Service`, but every request is tracked by a guard until it's answered. If the guard is
+// dropped without a response (e.g. because the task handling it panicked), the guard itself answers
+// with an error saying the request was dropped, even if the service is shutting down by then. A
+// plain `RequestHandle` gets the caller the same error, but only while the service is still up.
+pub struct RetryService<S: ServicePair> {
+    inner: Service<S>,
+}
+
+impl<S: ServicePair> RetryService<S> {
+    pub fn new(topic: impl AsRef<str>) -> RosResult<RetryService<S>> {
+        Ok(RetryService { inner: Service::new(topic)? })
+    }
+
+    // Returns `None` once the node is shutting down, or the service is gone.
+    #[inline]
+    pub async fn next_request(&mut self) -> Option<GuardedRequestHandle<S>> {
+        Some(GuardedRequestHandle { handle: Some(self.inner.next_request().await?) })
+    }
+}
+
+impl<S: ServicePair> std::ops::Deref for RetryService<S> {
+    type Target = rosrust::Service;
+
+    fn deref(&self) -> &rosrust::Service {
+        &self.inner
+    }
+}
+
+pub struct GuardedRequestHandle<S: ServicePair> {
+    // Only `None` after a response was sent.
+    handle: Option<RequestHandle<S>>,
+}
+
+impl<S: ServicePair> GuardedRequestHandle<S> {
+    pub fn request(&self) -> &S::Request {
+        self.handle.as_ref().unwrap().request()
+    }
+
+    pub fn send_ok(mut self, response: S::Response) {
+        self.handle.take().unwrap().send_ok(response);
+    }
+
+    pub async fn send_err(mut self, msg: impl Into<String>) {
+        self.handle.take().unwrap().send_err(msg).await;
+    }
+}
+
+impl<S: ServicePair> Drop for GuardedRequestHandle<S> {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.tx.send(Err(REQUEST_DROPPED.into()));
+        }
+    }
+}
+
+impl<S: ServicePair> std::fmt::Debug for GuardedRequestHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "GuardedRequestHandle {{..}}")
+    }
+}
+
+// Same as `Service`, but if a request isn't responded to within `timeout`, the caller gets an error
+// response. Responding after that is a no-op.
+pub struct TimeoutService<S: ServicePair> {
+    inner: Service<S>,
+    timeout: Duration,
+}
+
+impl<S: ServicePair> TimeoutService<S> {
+    pub async fn next_request(&mut self) -> Option<TimeoutRequestHandle<S>> {
+        let RequestHandle { request, tx } = self.inner.next_request().await?;
+        let tx = Arc::new(Mutex::new(Some(tx)));
+
+        let timeout = self.timeout;
+        let expired = Arc::clone(&tx);
+        tokio::spawn(async move {
+            time::sleep(timeout).await;
+            let expired = expired.lock().expect(FAILED_TO_LOCK).take();
+            if let Some(tx) = expired {
+                let _ = tx.send(Err(REQUEST_TIMED_OUT.into()));
+            }
+        });
+
+        Some(TimeoutRequestHandle { request, tx })
+    }
+}
+
+impl<S: ServicePair> std::ops::Deref for TimeoutService<S> {
+    type Target = rosrust::Service;
+
+    fn deref(&self) -> &rosrust::Service {
+        &self.inner
+    }
+}
+
+type SharedSender<S> = Arc<Mutex<Option<oneshot::Sender<Result<<S as ServicePair>::Response, String>>>>>;
+
+pub struct TimeoutRequestHandle<S: ServicePair> {
+    request: S::Request,
+    // Shared with the timeout task, whoever takes it first gets to respond.
+    tx: SharedSender<S>,
+}
+
+impl<S: ServicePair> TimeoutRequestHandle<S> {
+    pub fn request(&self) -> &S::Request {
+        &self.request
+    }
+
+    pub fn timed_out(&self) -> bool {
+        self.tx.lock().expect(FAILED_TO_LOCK).is_none()
+    }
+
+    pub fn send_ok(self, response: S::Response) {
+        self.send(Ok(response));
+    }
+
+    pub async fn send_err(self, msg: impl Into<String>) {
+        self.send(Err(msg.into()));
+    }
+
+    fn send(self, response: Result<S::Response, String>) {
+        let tx = self.tx.lock().expect(FAILED_TO_LOCK).take();
+        if let Some(tx) = tx {
+            let _ = tx.send(response);
+        }
+    }
+}
+
+impl<S: ServicePair> std::fmt::Debug for TimeoutRequestHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TimeoutRequestHandle {{..}}")
+    }
+}
+
+// Implemented by requests that carry a key identifying retries of the same request.
+pub trait HasIdempotencyKey {
+    fn idempotency_key(&self) -> String;
+}
+
+type ResponseCache<S> = Arc<Mutex<TtlCache<String, <S as ServicePair>::Response>>>;
+
+// Remembers up to `cache_size` responses for `ttl`, evicting the least recently used first. If a
+// request with an already answered key is received in that time, it is answered with the cached
+// response, and never handed out by `next_request`. Error responses are not cached.
+pub struct IdempotentService<S: ServicePair> {
+    inner: Service<S>,
+    cache: ResponseCache<S>,
+}
+
+impl<S> IdempotentService<S>
+where
+    S: ServicePair,
+    S::Request: HasIdempotencyKey,
+    S::Response: Clone,
+{
+    pub fn new(topic: impl AsRef<str>, cache_size: usize, ttl: Duration) -> RosResult<IdempotentService<S>> {
+        Ok(IdempotentService {
+            inner: Service::new(topic)?,
+            cache: Arc::new(Mutex::new(TtlCache::new(cache_size, ttl))),
+        })
+    }
+
+    pub async fn next_request(&mut self) -> Option<IdempotentRequestHandle<S>> {
+        loop {
+            let handle = self.inner.next_request().await?;
+            let key = handle.request().idempotency_key();
+            let cached = self.cache.lock().expect(FAILED_TO_LOCK).get(&key);
+
+            match cached {
+                Some(response) => handle.send_ok(response),
+                None => {
+                    return Some(IdempotentRequestHandle {
+                        handle,
+                        key,
+                        cache: Arc::clone(&self.cache),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl<S: ServicePair> std::ops::Deref for IdempotentService<S> {
+    type Target = rosrust::Service;
+
+    fn deref(&self) -> &rosrust::Service {
+        &self.inner
+    }
+}
+
+pub struct IdempotentRequestHandle<S: ServicePair> {
+    handle: RequestHandle<S>,
+    key: String,
+    cache: ResponseCache<S>,
+}
+
+impl<S> IdempotentRequestHandle<S>
+where
+    S: ServicePair,
+    S::Response: Clone,
+{
+    pub fn request(&self) -> &S::Request {
+        self.handle.request()
+    }
+
+    pub fn idempotency_key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn send_ok(self, response: S::Response) {
+        self.cache.lock().expect(FAILED_TO_LOCK).insert(self.key, response.clone());
+        self.handle.send_ok(response);
+    }
+
+    pub async fn send_err(self, msg: impl Into<String>) {
+        self.handle.send_err(msg).await;
+    }
+}
+
+impl<S: ServicePair> std::fmt::Debug for IdempotentRequestHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "IdempotentRequestHandle {{ key: {:?}, .. }}", self.key)
+    }
+}
+
+type RequestCache<S> = Arc<Mutex<TtlCache<<S as ServicePair>::Request, <S as ServicePair>::Response>>>;
+
+// For services without side effects, where the same request always gets the same response. Requests
+// that were already answered within `ttl` get the cached response right away, and are never handed
+// out by `next_request`. Up to `max_cache_size` responses are kept, error responses aren't cached.
+pub struct CachedService<S: ServicePair> {
+    inner: Service<S>,
+    cache: RequestCache<S>,
+}
+
+impl<S> CachedService<S>
+where
+    S: ServicePair,
+    S::Request: Hash + Eq + Clone,
+    S::Response: Clone,
+{
+    pub fn new(topic: impl AsRef<str>, max_cache_size: usize, ttl: Duration) -> RosResult<CachedService<S>> {
+        Ok(CachedService {
+            inner: Service::new(topic)?,
+            cache: Arc::new(Mutex::new(TtlCache::new(max_cache_size, ttl))),
+        })
+    }
+
+    pub async fn next_request(&mut self) -> Option<CachedRequestHandle<S>> {
+        loop {
+            let handle = self.inner.next_request().await?;
+            let cached = self.cache.lock().expect(FAILED_TO_LOCK).get(handle.request());
+            match cached {
+                Some(response) => handle.send_ok(response),
+                None => return Some(CachedRequestHandle { handle, cache: Arc::clone(&self.cache) }),
+            }
+        }
+    }
+}
+
+impl<S: ServicePair> std::ops::Deref for CachedService<S> {
+    type Target = rosrust::Service;
+
+    fn deref(&self) -> &rosrust::Service {
+        &self.inner
+    }
+}
+
+pub struct CachedRequestHandle<S: ServicePair> {
+    handle: RequestHandle<S>,
+    cache: RequestCache<S>,
+}
+
+impl<S> CachedRequestHandle<S>
+where
+    S: ServicePair,
+    S::Request: Hash + Eq + Clone,
+    S::Response: Clone,
+{
+    pub fn request(&self) -> &S::Request {
+        self.handle.request()
+    }
+
+    pub fn send_ok(self, response: S::Response) {
+        let request = self.handle.request().clone();
+        self.cache.lock().expect(FAILED_TO_LOCK).insert(request, response.clone());
+        self.handle.send_ok(response);
+    }
+
+    pub async fn send_err(self, msg: impl Into<String>) {
+        self.handle.send_err(msg).await;
+    }
+}
+
+impl<S: ServicePair> std::fmt::Debug for CachedRequestHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CachedRequestHandle {{..}}")
+    }
+}
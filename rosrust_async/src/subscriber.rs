@@ -1,8 +1,24 @@
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::marker::PhantomData;
 use std::ops::Deref;
-use tokio::sync::broadcast::{ self, error::RecvError };
+use std::time::Duration;
+use futures::stream::{self, Stream};
+use tokio::sync::broadcast::{ self, error::{ RecvError, TryRecvError } };
+use tokio::sync::mpsc;
+use tokio::time::{self, error::Elapsed};
 
 use rosrust::error::Result as RosResult;
-use rosrust::Message;
+use rosrust::{Message, RosMsg};
+
+// rosrust doesn't notify us about publishers going away, so the connections are polled instead.
+const CONNECTION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Connected { publisher_uri: String },
+    Disconnected { publisher_uri: String },
+}
 
 pub struct Subscriber<M> {
     rx: broadcast::Receiver<M>,
@@ -33,9 +49,229 @@ impl<M: Message> Subscriber<M> {
         }
     }
 
+    // `Err` means no message arrived in time, which is a good hint that the publishers stalled.
+    pub async fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<M>, Elapsed> {
+        time::timeout(timeout, self.recv()).await
+    }
+
     pub async fn try_recv(&mut self) -> Result<M, RecvError> {
         self.rx.recv().await
     }
+
+    // Doesn't wait, returns the newest message that arrived since the last call, dropping any
+    // older ones. Returns `None` if nothing arrived since.
+    pub fn latest(&mut self) -> Option<M> {
+        let mut latest = None;
+        loop {
+            match self.rx.try_recv() {
+                Ok(msg) => latest = Some(msg),
+                Err(TryRecvError::Lagged(_)) => (),
+                Err(_) => return latest,
+            }
+        }
+    }
+
+    // Same as calling `recv` in a loop, lagged messages are skipped. Clone the subscriber first if
+    // it's still needed, the stream keeps the subscription alive on its own.
+    pub fn into_stream(self) -> impl Stream<Item = M> + Send {
+        stream::unfold(self, |mut subscriber| async move {
+            let msg = subscriber.recv().await?;
+            Some((msg, subscriber))
+        })
+    }
+}
+
+impl<M> Subscriber<M> {
+    // Publishers that are already connected are reported as `Connected` events first. The stream
+    // ends when the node shuts down, and keeps the subscription alive for as long as it exists.
+    pub fn connection_events(&self) -> impl Stream<Item = ConnectionEvent> + Send + Unpin {
+        let raii = self.raii.clone();
+        let state = (raii, HashSet::new(), VecDeque::new());
+
+        Box::pin(stream::unfold(state, |(raii, mut known, mut pending)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((event, (raii, known, pending)));
+                }
+                if !rosrust::is_ok() {
+                    return None;
+                }
+
+                let current = raii.publisher_uris().into_iter().collect::<HashSet<String>>();
+                for publisher_uri in known.difference(&current) {
+                    pending.push_back(ConnectionEvent::Disconnected { publisher_uri: publisher_uri.clone() });
+                }
+                for publisher_uri in current.difference(&known) {
+                    pending.push_back(ConnectionEvent::Connected { publisher_uri: publisher_uri.clone() });
+                }
+                known = current;
+
+                if pending.is_empty() {
+                    time::sleep(CONNECTION_POLL_INTERVAL).await;
+                }
+            }
+        }))
+    }
+}
+
+impl<M: Message> Subscriber<M> {
+    pub fn with_latched_backfill(mut self) -> LatchedSubscriber<M> {
+        // Subscribed before picking up the messages that already arrived, so none can slip through
+        // in between.
+        let updates = self.tx.subscribe();
+        // A latched message may have already arrived before we got here, so pick it up right away.
+        let latest = self.latest();
+        LatchedSubscriber { inner: self, updates, latest }
+    }
+}
+
+// Keeps a copy of the last message received, so that the value of a latched topic can be accessed
+// at any time, even if it was only published once. The copy is kept up to date whether or not
+// `recv` is called.
+pub struct LatchedSubscriber<M> {
+    inner: Subscriber<M>,
+    // Gets every message as well, so `latest` doesn't take them away from `recv`.
+    updates: broadcast::Receiver<M>,
+    latest: Option<M>,
+}
+
+impl<M: Message> LatchedSubscriber<M> {
+    #[inline]
+    pub async fn recv(&mut self) -> Option<M> {
+        self.inner.recv().await
+    }
+
+    pub fn latest(&mut self) -> Option<&M> {
+        loop {
+            match self.updates.try_recv() {
+                Ok(msg) => self.latest = Some(msg),
+                Err(TryRecvError::Lagged(_)) => (),
+                Err(_) => return self.latest.as_ref(),
+            }
+        }
+    }
+}
+
+impl<M> Deref for LatchedSubscriber<M> {
+    type Target = rosrust::Subscriber;
+
+    fn deref(&self) -> &rosrust::Subscriber {
+        &self.inner
+    }
+}
+
+// Decodes the messages itself, so messages that fail to decode (e.g. because a publisher sends a
+// malformed body) are logged and skipped, instead of reaching the `recv` loop. Publishers of a
+// different message type are still rejected when connecting.
+pub struct SafeSubscriber<M> {
+    inner: Subscriber<M>,
+}
+
+impl<M: Message> SafeSubscriber<M> {
+    pub fn new(topic: impl AsRef<str>, queue_size: usize) -> RosResult<Self> {
+        let inner = Subscriber::subscribe_undecoded(topic, queue_size, |_| ())?;
+        Ok(SafeSubscriber { inner })
+    }
+
+    #[inline]
+    pub async fn recv(&mut self) -> Option<M> {
+        self.inner.recv().await
+    }
+}
+
+impl<M> Deref for SafeSubscriber<M> {
+    type Target = rosrust::Subscriber;
+
+    fn deref(&self) -> &rosrust::Subscriber {
+        &self.inner
+    }
+}
+
+// Receives the raw bytes of messages that failed to decode, for debugging what's actually being
+// published on the topic. Once it's full, further failed messages are only logged.
+pub struct DeadLetterQueue {
+    rx: mpsc::Receiver<Vec<u8>>,
+}
+
+impl DeadLetterQueue {
+    // Returns `None` once the subscription is gone.
+    pub async fn recv(&mut self) -> Option<Vec<u8>> {
+        self.rx.recv().await
+    }
+}
+
+impl<M: Message> Subscriber<M> {
+    // Works like `SafeSubscriber`, but hands the messages that failed to decode to the returned queue.
+    // Fails if `capacity` is 0, since the queue couldn't hold any message.
+    pub fn with_dead_letter_queue(topic: impl AsRef<str>, queue_size: usize, capacity: usize) -> RosResult<(Self, DeadLetterQueue)> {
+        if capacity == 0 {
+            return Err("The dead letter queue has to hold at least one message".into());
+        }
+        let (dead_tx, dead_rx) = mpsc::channel(capacity);
+        let subscriber = Subscriber::subscribe_undecoded(topic, queue_size, move |bytes| {
+            let _ = dead_tx.try_send(bytes);
+        })?;
+
+        Ok((subscriber, DeadLetterQueue { rx: dead_rx }))
+    }
+
+    // Subscribes with the type of `M`, but decodes the messages here instead of in rosrust, handing
+    // the bytes of the ones that fail to decode to `on_failure`.
+    fn subscribe_undecoded<F>(topic: impl AsRef<str>, queue_size: usize, on_failure: F) -> RosResult<Self>
+    where
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        let (tx, rx) = broadcast::channel(1);
+        let tx_clone = tx.clone();
+        let topic_name = topic.as_ref().to_string();
+        let raii = rosrust::subscribe(topic.as_ref(), queue_size, move |undecoded: Undecoded<M>| {
+            // The bytes don't start with the message length, so they are decoded as they are.
+            match M::decode(&undecoded.bytes[..]) {
+                // If the `send` fails, we don't care too much about it.
+                Ok(msg) => { let _ = tx_clone.send(msg); }
+                Err(err) => {
+                    rosrust::ros_warn!("Skipping message on {} that failed to decode: {}", topic_name, err);
+                    on_failure(undecoded.bytes);
+                }
+            }
+        })?;
+
+        Ok(Subscriber::create(rx, tx, raii))
+    }
+}
+
+// The bytes of a message of type `M`. Unlike `RawMessage`, it has the type and MD5 sum of `M`, so
+// rosrust only connects to publishers of `M`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Undecoded<M> {
+    bytes: Vec<u8>,
+    _msg: PhantomData<M>,
+}
+
+impl<M: Message> Message for Undecoded<M> {
+    fn msg_definition() -> String {
+        M::msg_definition()
+    }
+
+    fn md5sum() -> String {
+        M::md5sum()
+    }
+
+    fn msg_type() -> String {
+        M::msg_type()
+    }
+}
+
+impl<M> RosMsg for Undecoded<M> {
+    fn encode<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&self.bytes)
+    }
+
+    fn decode<R: io::Read>(mut r: R) -> io::Result<Self> {
+        let mut bytes = vec![];
+        r.read_to_end(&mut bytes)?;
+        Ok(Undecoded { bytes, _msg: PhantomData })
+    }
 }
 
 impl<M> Clone for Subscriber<M> {
@@ -0,0 +1,74 @@
+use std::time::Instant;
+
+use opentelemetry::trace::{Span, StatusCode, Tracer};
+use opentelemetry::{global, KeyValue};
+use rosrust::ServicePair;
+
+use crate::{RequestHandle, Service};
+
+// ROS services don't carry any metadata with the request, so there is no trace context to extract
+// or to propagate back to the caller. Every request starts a new root span instead.
+pub struct TracingService<S: ServicePair> {
+    inner: Service<S>,
+}
+
+impl<S: ServicePair> Service<S> {
+    pub fn with_tracing(self) -> TracingService<S> {
+        TracingService { inner: self }
+    }
+}
+
+impl<S: ServicePair> TracingService<S> {
+    pub async fn next_request(&mut self) -> Option<TracedRequestHandle<S>> {
+        let handle = self.inner.next_request().await?;
+        let service_type = S::msg_type();
+        let mut span = global::tracer("rosrust_async").start(service_type.clone());
+        span.set_attribute(KeyValue::new("ros.service.type", service_type));
+        Some(TracedRequestHandle { handle, span, start: Instant::now() })
+    }
+}
+
+impl<S: ServicePair> std::ops::Deref for TracingService<S> {
+    type Target = rosrust::Service;
+
+    fn deref(&self) -> &rosrust::Service {
+        &self.inner
+    }
+}
+
+// The span ends when the handle is dropped, even if no response was sent.
+pub struct TracedRequestHandle<S: ServicePair> {
+    handle: RequestHandle<S>,
+    span: global::BoxedSpan,
+    start: Instant,
+}
+
+impl<S: ServicePair> TracedRequestHandle<S> {
+    pub fn request(&self) -> &S::Request {
+        self.handle.request()
+    }
+
+    pub fn send_ok(mut self, response: S::Response) {
+        self.record_latency();
+        self.span.set_status(StatusCode::Ok, String::new());
+        self.handle.send_ok(response);
+    }
+
+    pub async fn send_err(mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        self.record_latency();
+        self.span.set_status(StatusCode::Error, msg.clone());
+        self.handle.send_err(msg).await;
+    }
+
+    fn record_latency(&mut self) {
+        let latency = self.start.elapsed().as_secs_f64() * 1000.0;
+        self.span.set_attribute(KeyValue::new("ros.service.latency_ms", latency));
+    }
+}
+
+impl<S: ServicePair> std::fmt::Debug for TracedRequestHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TracedRequestHandle {{..}}")
+    }
+}
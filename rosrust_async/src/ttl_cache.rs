@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+// Keeps up to `max_size` entries for `ttl` each. Least recently used entries are evicted first once
+// `max_size` is reached, and expired entries are never returned. A `max_size` of 0 disables caching.
+//
+// Every entry lives for the same `ttl`, so the insertion order is also the expiry order. Keeping
+// both orders indexed lets expired and least recently used entries be dropped without going through
+// the whole cache.
+pub(crate) struct TtlCache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    // Keys by the tick they were inserted at, oldest first.
+    by_insertion: BTreeMap<u64, K>,
+    // Keys by the tick they were last used at, least recently used first.
+    by_use: BTreeMap<u64, K>,
+    tick: u64,
+    max_size: usize,
+    ttl: Duration,
+}
+
+struct Entry<V> {
+    inserted_at: Instant,
+    inserted: u64,
+    used: u64,
+    value: V,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> TtlCache<K, V> {
+    pub(crate) fn new(max_size: usize, ttl: Duration) -> Self {
+        TtlCache {
+            entries: HashMap::new(),
+            by_insertion: BTreeMap::new(),
+            by_use: BTreeMap::new(),
+            tick: 0,
+            max_size,
+            ttl,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &K) -> Option<V> {
+        self.remove_expired();
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        let key = self.by_use.remove(&entry.used)?;
+        entry.used = tick;
+        self.by_use.insert(tick, key);
+        Some(entry.value.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if self.max_size == 0 {
+            return;
+        }
+        self.remove_expired();
+        self.remove(&key);
+        if self.entries.len() >= self.max_size {
+            let least_recently_used = self.by_use.values().next().cloned();
+            if let Some(least_recently_used) = least_recently_used {
+                self.remove(&least_recently_used);
+            }
+        }
+
+        let tick = self.next_tick();
+        self.by_insertion.insert(tick, key.clone());
+        self.by_use.insert(tick, key.clone());
+        let entry = Entry {
+            inserted_at: Instant::now(),
+            inserted: tick,
+            used: tick,
+            value,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    fn remove_expired(&mut self) {
+        while let Some((&inserted, oldest)) = self.by_insertion.iter().next() {
+            let ttl = self.ttl;
+            if let Some(entry) = self.entries.get(oldest) {
+                if entry.inserted_at.elapsed() < ttl {
+                    break;
+                }
+            }
+            let oldest = oldest.clone();
+            self.by_insertion.remove(&inserted);
+            self.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.by_insertion.remove(&entry.inserted);
+            self.by_use.remove(&entry.used);
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
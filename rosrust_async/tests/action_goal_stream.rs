@@ -0,0 +1,31 @@
+use futures::StreamExt;
+use rosrust_async::{ActionClient, ActionServer};
+use rosrust_msg::actionlib_tutorials::{FibonacciAction, FibonacciGoal, FibonacciResult};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn action_goal_stream_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ActionGoalStreamTest);
+    rosrust::init("action_goal_stream_test");
+
+    let server = ActionServer::<FibonacciAction>::new("fibonacci").unwrap();
+    let mut goals = server.goal_stream();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+    let mut goal = client.send_goal(FibonacciGoal { order: 1 }).await.unwrap();
+
+    let handle = goals.next().await.expect("Stream ended early");
+    assert_eq!(handle.goal().order, 1);
+    handle
+        .response_builder()
+        .result(FibonacciResult { sequence: vec![0] })
+        .send_succeeded();
+    goal.wait_for_result().await.unwrap();
+
+    rosrust::shutdown();
+    let end = tokio::time::timeout(Duration::from_secs(5), goals.next()).await;
+    assert!(end.unwrap().is_none());
+}
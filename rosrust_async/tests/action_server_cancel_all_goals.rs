@@ -0,0 +1,45 @@
+use rosrust_actionlib::GoalState;
+use rosrust_async::{ActionClient, ActionServer};
+use rosrust_msg::actionlib_tutorials::{FibonacciAction, FibonacciGoal, FibonacciResult};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn action_server_cancel_all_goals_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ActionServerCancelAllGoalsTest);
+    rosrust::init("action_server_cancel_all_goals_test");
+
+    let mut server = ActionServer::<FibonacciAction>::new("fibonacci").unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+    let mut goals = vec![];
+    for order in 0..3 {
+        goals.push(client.send_goal(FibonacciGoal { order }).await.unwrap());
+    }
+
+    while server.stats().goals_received < 3 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    // Goals are counted right before they are queued.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    server.cancel_all_goals();
+
+    for goal in &mut goals {
+        let _ = tokio::time::timeout(Duration::from_secs(10), goal.wait_for_result())
+            .await
+            .unwrap();
+        assert_eq!(goal.goal_state(), GoalState::Preempted);
+    }
+
+    // Goals sent afterwards are handed out as usual.
+    let mut goal = client.send_goal(FibonacciGoal { order: 5 }).await.unwrap();
+    let handle = server.recv().await;
+    assert_eq!(handle.goal().order, 5);
+    handle
+        .response_builder()
+        .result(FibonacciResult { sequence: vec![0] })
+        .send_succeeded();
+    assert_eq!(goal.wait_for_result().await.unwrap().sequence, vec![0]);
+}
@@ -0,0 +1,46 @@
+use rosrust_async::{ActionClient, ActionServer, ActionServerStats};
+use rosrust_msg::actionlib_tutorials::{FibonacciAction, FibonacciGoal, FibonacciResult};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn action_server_stats_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ActionServerStatsTest);
+    rosrust::init("action_server_stats_test");
+
+    let mut server = ActionServer::<FibonacciAction>::new("fibonacci").unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+    assert_eq!(server.stats(), ActionServerStats::default());
+
+    for order in 0..3 {
+        let mut goal = client.send_goal(FibonacciGoal { order }).await.unwrap();
+        let handle = server.recv().await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        match order {
+            0 => handle
+                .response_builder()
+                .result(FibonacciResult { sequence: vec![0] })
+                .send_succeeded(),
+            1 => handle.response_builder().text("failed").send_aborted(),
+            _ => handle.response_builder().text("stopped").send_canceled(),
+        };
+        // Goals are counted once their handle is gone.
+        drop(handle);
+        let _ = tokio::time::timeout(Duration::from_secs(10), goal.wait_for_result())
+            .await
+            .unwrap();
+    }
+
+    let stats = server.stats();
+    assert_eq!(stats.goals_received, 3);
+    assert_eq!(stats.goals_completed, 1);
+    assert_eq!(stats.goals_aborted, 1);
+    assert_eq!(stats.goals_preempted, 1);
+    assert!(stats.mean_execution_time_ms >= 100.0);
+
+    server.reset_stats();
+    assert_eq!(server.stats(), ActionServerStats::default());
+}
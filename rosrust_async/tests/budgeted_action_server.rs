@@ -0,0 +1,63 @@
+use rosrust_actionlib::GoalState;
+use rosrust_async::{ActionClient, BudgetedActionServer};
+use rosrust_msg::actionlib_tutorials::{FibonacciAction, FibonacciGoal, FibonacciResult};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn budgeted_action_server_test() {
+    let _roscore = util::run_roscore_for(util::Feature::BudgetedActionServerTest);
+    rosrust::init("budgeted_action_server_test");
+
+    let mut server =
+        BudgetedActionServer::<FibonacciAction>::new("fibonacci", Duration::from_millis(200))
+            .unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+
+    let mut goal = client.send_goal(FibonacciGoal { order: 1 }).await.unwrap();
+    let handle = server.recv().await;
+    assert_eq!(handle.goal().order, 1);
+    assert!(!handle.timed_out().await);
+    assert!(
+        handle
+            .set_succeeded(FibonacciResult { sequence: vec![0] })
+            .await
+    );
+    goal.wait_for_result().await.unwrap();
+    assert_eq!(goal.goal_state(), GoalState::Succeeded);
+
+    let mut goal = client.send_goal(FibonacciGoal { order: 2 }).await.unwrap();
+    let handle = server.recv().await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(handle.timed_out().await);
+    assert!(
+        !handle
+            .set_succeeded(FibonacciResult {
+                sequence: vec![0, 1]
+            })
+            .await
+    );
+    let _ = goal.wait_for_result().await;
+    assert_eq!(goal.goal_state(), GoalState::Aborted);
+
+    // The cancel has to arrive well within the budget, so it's given a server of its own.
+    let mut server =
+        BudgetedActionServer::<FibonacciAction>::new("fibonacci_slow", Duration::from_secs(30))
+            .unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci_slow")
+        .await
+        .unwrap();
+
+    let mut goal = client.send_goal(FibonacciGoal { order: 3 }).await.unwrap();
+    let handle = server.recv().await;
+    goal.cancel_goal();
+    while !handle.canceled().await {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(handle.set_preempted("Canceled by the client").await);
+    let _ = goal.wait_for_result().await;
+    assert_eq!(goal.goal_state(), GoalState::Preempted);
+}
@@ -0,0 +1,52 @@
+use rosrust_async::CachedService;
+use std::time::Duration;
+use util::echo_service::{call_echo, Echo, EchoRes};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cached_service_test() {
+    let _roscore = util::run_roscore_for(util::Feature::CachedServiceTest);
+    rosrust::init("cached_service_test");
+
+    let mut service = CachedService::<Echo>::new("/echo", 2, Duration::from_secs(60)).unwrap();
+
+    for data in &["a", "b"] {
+        let call = call_echo("/echo", data);
+        let handle = service.next_request().await.expect("Service stopped early");
+        assert_eq!(handle.request().data, *data);
+        handle.send_ok(EchoRes {
+            data: data.to_uppercase(),
+        });
+        assert_eq!(call.await.unwrap().unwrap(), data.to_uppercase());
+    }
+
+    // Answered from the cache, which also makes "a" the most recently used entry.
+    let call = call_echo("/echo", "a");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), service.next_request())
+            .await
+            .is_err()
+    );
+    assert_eq!(call.await.unwrap().unwrap(), "A");
+
+    // Caching "c" evicts "b", the least recently used entry.
+    let call = call_echo("/echo", "c");
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_ok(EchoRes { data: "C".into() });
+    assert_eq!(call.await.unwrap().unwrap(), "C");
+
+    let call = call_echo("/echo", "a");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), service.next_request())
+            .await
+            .is_err()
+    );
+    assert_eq!(call.await.unwrap().unwrap(), "A");
+
+    let call = call_echo("/echo", "b");
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.request().data, "b");
+    handle.send_ok(EchoRes { data: "B".into() });
+    assert_eq!(call.await.unwrap().unwrap(), "B");
+}
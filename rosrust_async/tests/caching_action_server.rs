@@ -0,0 +1,46 @@
+use rosrust_actionlib::GoalState;
+use rosrust_async::{ActionClient, CachingActionServer};
+use rosrust_msg::actionlib_tutorials::{FibonacciAction, FibonacciGoal, FibonacciResult};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn caching_action_server_test() {
+    let _roscore = util::run_roscore_for(util::Feature::CachingActionServerTest);
+    rosrust::init("caching_action_server_test");
+
+    let mut server =
+        CachingActionServer::<FibonacciAction>::new("fibonacci", Duration::from_secs(60)).unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+
+    let mut goal = client.send_goal(FibonacciGoal { order: 3 }).await.unwrap();
+    let handle = server.recv().await;
+    assert!(handle.send_succeeded(FibonacciResult {
+        sequence: vec![0, 1, 1]
+    }));
+    assert_eq!(
+        goal.wait_for_result().await.unwrap().sequence,
+        vec![0, 1, 1]
+    );
+
+    // Same goal content, but a new goal ID. The cached result is sent while `recv` waits for the
+    // next goal, which never comes.
+    let mut goal = client.send_goal(FibonacciGoal { order: 3 }).await.unwrap();
+    let recv = tokio::time::timeout(Duration::from_secs(2), server.recv()).await;
+    assert!(recv.is_err());
+    assert_eq!(
+        goal.wait_for_result().await.unwrap().sequence,
+        vec![0, 1, 1]
+    );
+    assert_eq!(goal.goal_state(), GoalState::Succeeded);
+
+    // A different goal isn't cached, so it's handed out.
+    let _goal = client.send_goal(FibonacciGoal { order: 4 }).await.unwrap();
+    let handle = tokio::time::timeout(Duration::from_secs(10), server.recv())
+        .await
+        .unwrap();
+    assert_eq!(handle.goal().order, 4);
+}
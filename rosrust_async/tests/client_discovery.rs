@@ -0,0 +1,39 @@
+use rosrust_async::Client;
+use rosrust_msg::std_srvs::{Trigger, TriggerRes};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_discovery_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ClientDiscoveryTest);
+    rosrust::init("client_discovery_test");
+
+    let handler = |_| {
+        Ok(TriggerRes {
+            success: true,
+            message: "found".into(),
+        })
+    };
+    let _first = rosrust::service::<Trigger, _>("/first_trigger", handler).unwrap();
+    let _second = rosrust::service::<Trigger, _>("/second_trigger", handler).unwrap();
+
+    let mut services = Client::<Trigger>::discover("std_srvs/Trigger")
+        .await
+        .unwrap();
+    services.sort();
+    assert_eq!(services, vec!["/first_trigger", "/second_trigger"]);
+    assert!(Client::<Trigger>::discover("std_srvs/Empty")
+        .await
+        .unwrap()
+        .is_empty());
+
+    let client = Client::<Trigger>::first_available("std_srvs/Trigger")
+        .await
+        .unwrap();
+    let response = client.req(Default::default()).await.unwrap().unwrap();
+    assert_eq!(response.message, "found");
+
+    assert!(Client::<Trigger>::first_available("std_srvs/Empty")
+        .await
+        .is_err());
+}
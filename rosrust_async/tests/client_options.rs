@@ -0,0 +1,69 @@
+use rosrust_async::{Client, ClientOptions};
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod util;
+
+// Fails the first request, and succeeds on every one after that.
+fn serve(calls: Arc<AtomicUsize>) -> rosrust::Service {
+    rosrust::service::<Trigger, _>("/flaky", move |_| {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 => Err("busy".into()),
+            _ => Ok(TriggerRes {
+                success: true,
+                message: "done".into(),
+            }),
+        }
+    })
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_options_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ClientOptionsTest);
+    rosrust::init("client_options_test");
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let service = serve(Arc::clone(&calls));
+    // Factors that would shrink the delay are raised to 1.
+    assert_eq!(
+        ClientOptions::new().backoff_factor(0.5),
+        ClientOptions::new().backoff_factor(1.0)
+    );
+    assert_eq!(
+        ClientOptions::new().backoff_factor(f64::NAN),
+        ClientOptions::new().backoff_factor(1.0)
+    );
+
+    // The delay goes straight to `max_delay`, instead of overflowing.
+    let options = ClientOptions::new()
+        .initial_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_millis(500))
+        .backoff_factor(f64::INFINITY)
+        .max_retries(20);
+    let client = Client::<Trigger>::with_options("/flaky", options)
+        .await
+        .unwrap();
+
+    // The service got the request, so its error is the response, and it isn't asked again.
+    let response = client.req(TriggerReq {}).await.unwrap();
+    assert_eq!(response.unwrap_err(), "busy");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Restart the service, so the client can only reach it by looking it up again.
+    drop(service);
+    let restart_calls = Arc::clone(&calls);
+    let restarted = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        serve(restart_calls)
+    });
+
+    let response = client.req(TriggerReq {}).await.unwrap().unwrap();
+    assert!(response.success);
+    assert_eq!(response.message, "done");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    let _service = restarted.await.unwrap();
+}
@@ -0,0 +1,47 @@
+use rosrust::api::error::tcpros::ErrorKind;
+use rosrust_async::Client;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::{Duration, Instant};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_req_timeout_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ClientReqTimeoutTest);
+    rosrust::init("client_req_timeout_test");
+
+    // Stands in for a service provider that stops responding in the middle of a call.
+    let service = rosrust::service::<Trigger, _>("/hanging", |_| {
+        std::thread::sleep(Duration::from_secs(10));
+        Ok(TriggerRes::default())
+    })
+    .unwrap();
+
+    let client = Client::<Trigger>::new("/hanging").await.unwrap();
+    let start = Instant::now();
+    let call = tokio::spawn(async move {
+        client
+            .req_timeout(TriggerReq {}, Duration::from_millis(500))
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    drop(service);
+
+    let err = call.await.unwrap().unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(matches!(err.kind(), ErrorKind::ServiceResponseTimeout));
+
+    let _working =
+        rosrust::service::<Trigger, _>("/working", |_| Ok(TriggerRes::default())).unwrap();
+    let results = tokio::task::spawn_blocking(|| {
+        let client = rosrust::client::<Trigger>("/working").unwrap();
+        (
+            client.req_with_timeout(&TriggerReq {}, Duration::from_secs(0)),
+            client.req_with_timeout(&TriggerReq {}, Duration::from_secs(5)),
+        )
+    })
+    .await
+    .unwrap();
+    assert!(results.0.is_err());
+    assert!(results.1.unwrap().is_ok());
+}
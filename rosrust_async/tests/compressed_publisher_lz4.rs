@@ -0,0 +1,41 @@
+#![cfg(feature = "lz4")]
+
+use rosrust::{RawMessage, RosMsg};
+use rosrust_async::{LZ4Compressor, Publisher, Subscriber};
+use rosrust_msg::std_msgs::String as RosString;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lz4_compressed_publisher_test() {
+    let _roscore = util::run_roscore_for(util::Feature::Lz4CompressedPublisherTest);
+    rosrust::init("lz4_compressed_publisher_test");
+
+    let mut subscriber = Subscriber::<RawMessage>::new("chatter", 10).unwrap();
+    let publisher = Publisher::<RosString>::new("chatter", 10).unwrap();
+    assert!(publisher
+        .clone()
+        .with_compression(LZ4Compressor)
+        .await
+        .is_err());
+    let mut publisher = publisher.with_compression(LZ4Compressor).await.unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let message = RosString {
+        data: "hello ".repeat(100),
+    };
+    publisher.send(message.clone()).await.unwrap();
+
+    let raw = tokio::time::timeout(Duration::from_secs(10), subscriber.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    // Skip the length of the `uint8[] data` array, the rest is the compressed message.
+    let compressed = &raw.0[4..];
+    assert!(compressed.len() < message.data.len());
+    let bytes = lz4_flex::decompress_size_prepended(compressed).unwrap();
+    assert_eq!(RosString::decode(&bytes[..]).unwrap(), message);
+}
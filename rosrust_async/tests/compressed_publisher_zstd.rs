@@ -0,0 +1,44 @@
+#![cfg(feature = "zstd")]
+
+use rosrust::{RawMessage, RosMsg};
+use rosrust_async::{Publisher, Subscriber, ZstdCompressor};
+use rosrust_msg::std_msgs::String as RosString;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn zstd_compressed_publisher_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ZstdCompressedPublisherTest);
+    rosrust::init("zstd_compressed_publisher_test");
+
+    let mut subscriber = Subscriber::<RawMessage>::new("chatter", 10).unwrap();
+    let publisher = Publisher::<RosString>::new("chatter", 10).unwrap();
+    assert!(publisher
+        .clone()
+        .with_compression(ZstdCompressor::default())
+        .await
+        .is_err());
+    let mut publisher = publisher
+        .with_compression(ZstdCompressor::default())
+        .await
+        .unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let message = RosString {
+        data: "hello ".repeat(100),
+    };
+    publisher.send(message.clone()).await.unwrap();
+
+    let raw = tokio::time::timeout(Duration::from_secs(10), subscriber.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    // Skip the length of the `uint8[] data` array, the rest is the compressed message.
+    let compressed = &raw.0[4..];
+    assert!(compressed.len() < message.data.len());
+    let bytes = zstd_rs::decode_all(compressed).unwrap();
+    assert_eq!(RosString::decode(&bytes[..]).unwrap(), message);
+}
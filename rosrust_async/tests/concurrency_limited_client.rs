@@ -0,0 +1,42 @@
+use futures::future::join_all;
+use rosrust_async::ConcurrencyLimitedClient;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrency_limited_client_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ConcurrencyLimitedClientTest);
+    rosrust::init("concurrency_limited_client_test");
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (service_in_flight, service_peak) = (Arc::clone(&in_flight), Arc::clone(&peak));
+    let _service = rosrust::service::<Trigger, _>("/slow", move |_| {
+        let current = service_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        service_peak.fetch_max(current, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(200));
+        service_in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(TriggerRes {
+            success: true,
+            message: String::new(),
+        })
+    })
+    .unwrap();
+
+    assert!(ConcurrencyLimitedClient::<Trigger>::new("/slow", 0)
+        .await
+        .is_err());
+
+    let client = ConcurrencyLimitedClient::<Trigger>::new("/slow", 2)
+        .await
+        .unwrap();
+    let responses = join_all((0..6).map(|_| client.req(TriggerReq {}))).await;
+    for response in responses {
+        assert!(response.unwrap().unwrap().success);
+    }
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,93 @@
+use rosrust_async::{Client, ClientHooks, ClientOptions, Service};
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod util;
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+    latencies: Arc<Mutex<Vec<Duration>>>,
+}
+
+impl ClientHooks<Trigger> for Recorder {
+    fn on_request(&self, _req: &TriggerReq) {
+        self.events.lock().unwrap().push("request".into());
+    }
+
+    fn on_response(&self, _req: &TriggerReq, resp: &Result<TriggerRes, String>, latency: Duration) {
+        let event = match resp {
+            Ok(resp) => format!("ok: {}", resp.message),
+            Err(err) => format!("err: {}", err),
+        };
+        self.events.lock().unwrap().push(event);
+        self.latencies.lock().unwrap().push(latency);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn hooked_client_test() {
+    let _roscore = util::run_roscore_for(util::Feature::HookedClientTest);
+    rosrust::init("hooked_client_test");
+
+    let mut service = Service::<Trigger>::new("/trigger").unwrap();
+    let recorder = Recorder::default();
+    let options = ClientOptions::new()
+        .initial_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_millis(500))
+        .max_retries(20);
+    let client = Client::<Trigger>::with_options("/trigger", options)
+        .await
+        .unwrap()
+        .with_hooks(recorder.clone());
+
+    let call = {
+        let client = client.clone();
+        tokio::spawn(async move { client.req(TriggerReq {}).await })
+    };
+    let handle = service.next_request().await.expect("Service stopped early");
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    assert_eq!(call.await.unwrap().unwrap().unwrap().message, "done");
+
+    let call = {
+        let client = client.clone();
+        tokio::spawn(async move { client.req(TriggerReq {}).await })
+    };
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_err("failed").await;
+    assert_eq!(call.await.unwrap().unwrap().unwrap_err(), "failed");
+
+    // Requests are retried by the wrapped client until the service is back.
+    drop(service);
+    let restarted = tokio::spawn(async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        rosrust::service::<Trigger, _>("/trigger", |_| {
+            Ok(TriggerRes {
+                success: true,
+                message: "restarted".into(),
+            })
+        })
+        .unwrap()
+    });
+    let response = client.req(TriggerReq {}).await.unwrap().unwrap();
+    assert_eq!(response.message, "restarted");
+    let _service = restarted.await.unwrap();
+
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec![
+            "request",
+            "ok: done",
+            "request",
+            "err: failed",
+            "request",
+            "ok: restarted"
+        ]
+    );
+    assert!(recorder.latencies.lock().unwrap()[0] >= Duration::from_millis(200));
+}
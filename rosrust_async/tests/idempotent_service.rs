@@ -0,0 +1,64 @@
+use rosrust_async::IdempotentService;
+use std::time::Duration;
+use util::echo_service::{call_echo, Echo, EchoRes};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn idempotent_service_test() {
+    let _roscore = util::run_roscore_for(util::Feature::IdempotentServiceTest);
+    rosrust::init("idempotent_service_test");
+
+    let ttl = Duration::from_secs(3);
+    let mut service = IdempotentService::<Echo>::new("/echo", 1, ttl).unwrap();
+
+    let call = call_echo("/echo", "a");
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.idempotency_key(), "a");
+    handle.send_ok(EchoRes {
+        data: "first".into(),
+    });
+    assert_eq!(call.await.unwrap().unwrap(), "first");
+
+    // Retries are answered from the cache, without reaching `next_request`.
+    let call = call_echo("/echo", "a");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), service.next_request())
+            .await
+            .is_err()
+    );
+    assert_eq!(call.await.unwrap().unwrap(), "first");
+
+    // Errors are not cached.
+    let call = call_echo("/echo", "b");
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_err("failed").await;
+    assert_eq!(call.await.unwrap().unwrap_err(), "failed");
+
+    let call = call_echo("/echo", "b");
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.idempotency_key(), "b");
+    handle.send_ok(EchoRes {
+        data: "second".into(),
+    });
+    assert_eq!(call.await.unwrap().unwrap(), "second");
+
+    // The cache only fits one response, so "a" was evicted by "b".
+    let call = call_echo("/echo", "a");
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.idempotency_key(), "a");
+    handle.send_ok(EchoRes {
+        data: "third".into(),
+    });
+    assert_eq!(call.await.unwrap().unwrap(), "third");
+
+    // Responses expire after the TTL.
+    tokio::time::sleep(ttl).await;
+    let call = call_echo("/echo", "a");
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.idempotency_key(), "a");
+    handle.send_ok(EchoRes {
+        data: "fourth".into(),
+    });
+    assert_eq!(call.await.unwrap().unwrap(), "fourth");
+}
@@ -0,0 +1,33 @@
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::Int32;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inline_publisher_test() {
+    let _roscore = util::run_roscore_for(util::Feature::InlinePublisherTest);
+    rosrust::init("inline_publisher_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: Int32| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let publisher = Publisher::<Int32>::new("chatter", 100).unwrap();
+    assert_eq!(publisher.topic(), "/chatter");
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    let mut publisher = publisher.with_inline_send();
+    for data in 0..50 {
+        publisher.send(Int32 { data }).await.unwrap();
+    }
+
+    let mut received = Vec::new();
+    while received.len() < 50 {
+        received.push(rx.recv_timeout(Duration::from_secs(10)).unwrap());
+    }
+    assert_eq!(received, (0..50).collect::<Vec<i32>>());
+}
@@ -0,0 +1,42 @@
+use rosrust_async::{Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn latched_subscriber_test() {
+    let _roscore = util::run_roscore_for(util::Feature::LatchedSubscriberTest);
+    rosrust::init("latched_subscriber_test");
+
+    let mut publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    publisher.set_latching(true);
+    publisher.send(Int32 { data: 1 }).await.unwrap();
+
+    // The latched message arrives as soon as the subscriber connects.
+    let subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let mut subscriber = subscriber.with_latched_backfill();
+    assert_eq!(subscriber.latest(), Some(&Int32 { data: 1 }));
+
+    // The latest message is kept up to date without calling `recv`.
+    publisher.send(Int32 { data: 2 }).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while subscriber.latest() != Some(&Int32 { data: 2 }) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // Reading the latest message doesn't take it away from `recv`.
+    let msg = tokio::time::timeout(Duration::from_secs(10), subscriber.recv())
+        .await
+        .unwrap();
+    assert_eq!(msg, Some(Int32 { data: 2 }));
+    assert_eq!(subscriber.latest(), Some(&Int32 { data: 2 }));
+    // Reading the latest message doesn't consume it.
+    assert_eq!(subscriber.latest(), Some(&Int32 { data: 2 }));
+}
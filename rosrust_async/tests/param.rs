@@ -0,0 +1,34 @@
+use futures::StreamExt;
+use rosrust_async::param;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn param_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ParamTest);
+    rosrust::init("param_test");
+
+    assert_eq!(param::get::<i32>("/answer").await.unwrap(), None);
+    param::set("/answer", 42).await.unwrap();
+    assert_eq!(param::get::<i32>("/answer").await.unwrap(), Some(42));
+    param::delete("/answer").await.unwrap();
+    assert_eq!(param::get::<i32>("/answer").await.unwrap(), None);
+
+    param::set("/watched", String::from("a")).await.unwrap();
+    let values = param::watch_with_interval::<String>("/watched", Duration::from_millis(10))
+        .await
+        .unwrap();
+    tokio::pin!(values);
+    assert_eq!(values.next().await.as_deref(), Some("a"));
+
+    param::set("/watched", String::from("a")).await.unwrap();
+    param::set("/watched", String::from("b")).await.unwrap();
+    let next = tokio::time::timeout(Duration::from_secs(5), values.next())
+        .await
+        .expect("Parameter change was never observed");
+    assert_eq!(next.as_deref(), Some("b"));
+
+    rosrust::shutdown();
+    assert_eq!(values.next().await, None);
+}
@@ -0,0 +1,52 @@
+use futures::future::join_all;
+use rosrust_async::PooledClient;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pooled_client_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PooledClientTest);
+    rosrust::init("pooled_client_test");
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    // Every connection is handled by a thread of its own, so the threads tell connections apart.
+    let connections = Arc::new(Mutex::new(HashSet::new()));
+    let (service_in_flight, service_peak, service_connections) = (
+        Arc::clone(&in_flight),
+        Arc::clone(&peak),
+        Arc::clone(&connections),
+    );
+    let _service = rosrust::service::<Trigger, _>("/slow", move |_| {
+        service_connections
+            .lock()
+            .unwrap()
+            .insert(std::thread::current().id());
+        let current = service_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        service_peak.fetch_max(current, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(200));
+        service_in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(TriggerRes {
+            success: true,
+            message: String::new(),
+        })
+    })
+    .unwrap();
+
+    assert!(PooledClient::<Trigger>::new("/slow", 0, 2).await.is_err());
+    assert!(PooledClient::<Trigger>::new("/slow", 2, 0).await.is_err());
+
+    let client = PooledClient::<Trigger>::new("/slow", 2, 2).await.unwrap();
+    let responses = join_all((0..6).map(|_| client.req(TriggerReq {}))).await;
+    for response in responses {
+        assert!(response.unwrap().unwrap().success);
+    }
+    assert!(client.req(TriggerReq {}).await.unwrap().unwrap().success);
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(connections.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,51 @@
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::Int32;
+use std::future::ready;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_select_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSelectTest);
+    rosrust::init("publisher_select_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: Int32| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let mut publisher = Publisher::<Int32>::new("chatter", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    for i in 0..30 {
+        let message = Int32 { data: i };
+        match i % 3 {
+            // The send future is dropped without ever being polled, so nothing is sent.
+            0 => tokio::select! {
+                biased;
+                _ = ready(()) => {}
+                _ = publisher.send(message) => {}
+            },
+            // The send future is polled once and then dropped, the message still goes out.
+            1 => tokio::select! {
+                biased;
+                _ = publisher.send(message) => {}
+                _ = ready(()) => {}
+            },
+            _ => publisher.send(message).await.unwrap(),
+        }
+    }
+
+    let expected: Vec<i32> = (0..30).filter(|i| i % 3 != 0).collect();
+    let mut received = Vec::new();
+    while received.len() < expected.len() {
+        received.push(rx.recv_timeout(Duration::from_secs(10)).unwrap());
+    }
+    assert_eq!(received, expected);
+    assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+}
@@ -0,0 +1,41 @@
+use bytes::Bytes;
+use rosrust::RosMsg;
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::String as RosString;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_send_raw_bytes_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSendRawBytesTest);
+    rosrust::init("publisher_send_raw_bytes_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: RosString| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let mut publisher = Publisher::<RosString>::new("chatter", 100).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    // Serialized without the length prefix, which is added when sending.
+    let mut body = vec![];
+    RosString {
+        data: "hello".into(),
+    }
+    .encode(&mut body)
+    .unwrap();
+    publisher.send_raw_bytes(Bytes::from(body)).await.unwrap();
+    publisher
+        .send(RosString {
+            data: "world".into(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), "hello");
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), "world");
+}
@@ -0,0 +1,45 @@
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::Int32;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_send_with_confirmation_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSendWithConfirmationTest);
+    rosrust::init("publisher_send_with_confirmation_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: Int32| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let publisher = Publisher::<Int32>::new("chatter", 100).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    // Doesn't need an async context.
+    let (confirmed_tx, confirmed_rx) = mpsc::channel();
+    let thread_publisher = publisher.clone();
+    thread::spawn(move || {
+        for data in 0..10 {
+            let confirmed_tx = confirmed_tx.clone();
+            thread_publisher
+                .send_with_confirmation(Int32 { data }, move || confirmed_tx.send(data).unwrap())
+                .unwrap();
+        }
+    })
+    .join()
+    .unwrap();
+
+    let mut confirmed = Vec::new();
+    let mut received = Vec::new();
+    while received.len() < 10 {
+        confirmed.push(confirmed_rx.recv_timeout(Duration::from_secs(10)).unwrap());
+        received.push(rx.recv_timeout(Duration::from_secs(10)).unwrap());
+    }
+    assert_eq!(confirmed, (0..10).collect::<Vec<i32>>());
+    assert_eq!(received, (0..10).collect::<Vec<i32>>());
+}
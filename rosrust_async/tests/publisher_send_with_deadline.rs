@@ -0,0 +1,55 @@
+use rosrust::error::ErrorKind;
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::Int32;
+use std::sync::mpsc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_send_with_deadline_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSendWithDeadlineTest);
+    rosrust::init("publisher_send_with_deadline_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: Int32| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let mut publisher = Publisher::<Int32>::new("chatter", 100).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    publisher
+        .send_with_deadline(Int32 { data: 0 }, deadline)
+        .await
+        .unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 0);
+
+    // Holds up the send queue, so the next send can't make its deadline.
+    publisher
+        .send_with_confirmation(Int32 { data: 1 }, || {
+            std::thread::sleep(Duration::from_secs(1))
+        })
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_millis(100);
+    let err = publisher
+        .send_with_deadline(Int32 { data: 2 }, deadline)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::TimeoutError));
+    assert!(Instant::now() < deadline + Duration::from_millis(500));
+
+    // The message was still queued at the deadline, so it's dropped instead of going out late.
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 1);
+    assert!(rx.recv_timeout(Duration::from_secs(2)).is_err());
+
+    // Sends with a deadline that is met still go out in order.
+    publisher
+        .send_with_deadline(Int32 { data: 3 }, Instant::now() + Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), 3);
+}
@@ -0,0 +1,72 @@
+use rosrust::{Message, RosMsg};
+use rosrust_async::{HasHeader, Publisher};
+use rosrust_msg::std_msgs::Header;
+use std::io;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod util;
+
+// Generated messages can't implement `HasHeader` outside of `rosrust_async`, so this one is sent as
+// a bare `std_msgs/Header`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Stamped(Header);
+
+impl Message for Stamped {
+    fn msg_definition() -> String {
+        Header::msg_definition()
+    }
+
+    fn md5sum() -> String {
+        Header::md5sum()
+    }
+
+    fn msg_type() -> String {
+        Header::msg_type()
+    }
+}
+
+impl RosMsg for Stamped {
+    fn encode<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.0.encode(w)
+    }
+
+    fn decode<R: io::Read>(r: R) -> io::Result<Self> {
+        Ok(Stamped(RosMsg::decode(r)?))
+    }
+}
+
+impl HasHeader for Stamped {
+    fn header_mut(&mut self) -> &mut Header {
+        &mut self.0
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_send_with_header_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSendWithHeaderTest);
+    rosrust::init("publisher_send_with_header_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |header: Header| {
+        tx.send(header).unwrap();
+    })
+    .unwrap();
+
+    let mut publisher = Publisher::<Stamped>::new("chatter", 100).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    let before = rosrust::now();
+    let mut message = Stamped::default();
+    message.0.frame_id = "overwritten".into();
+    publisher
+        .send_with_header(message, "base_link")
+        .await
+        .unwrap();
+    let after = rosrust::now();
+
+    let header = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(header.frame_id, "base_link");
+    assert!(header.stamp >= before);
+    assert!(header.stamp <= after);
+}
@@ -0,0 +1,34 @@
+use futures::{stream, SinkExt};
+use rosrust_async::Publisher;
+use rosrust_msg::std_msgs::Int32;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_sink_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherSinkTest);
+    rosrust::init("publisher_sink_test");
+
+    let (tx, rx) = mpsc::channel();
+    let _subscriber = rosrust::subscribe("chatter", 100, move |message: Int32| {
+        tx.send(message.data).unwrap();
+    })
+    .unwrap();
+
+    let publisher = Publisher::<Int32>::new("chatter", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let mut sink = publisher.into_sink();
+    let mut messages = stream::iter((0..50).map(|data| Ok(Int32 { data })));
+    sink.send_all(&mut messages).await.unwrap();
+
+    let mut received = Vec::new();
+    while received.len() < 50 {
+        received.push(rx.recv_timeout(Duration::from_secs(10)).unwrap());
+    }
+    assert_eq!(received, (0..50).collect::<Vec<i32>>());
+}
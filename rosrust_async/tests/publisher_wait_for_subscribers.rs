@@ -0,0 +1,37 @@
+use rosrust_async::{Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publisher_wait_for_subscribers_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherWaitForSubscribersTest);
+    rosrust::init("publisher_wait_for_subscribers_test");
+
+    let publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    publisher.wait_for_subscribers(0).await.unwrap();
+    assert!(publisher
+        .wait_for_subscribers_with_interval(0, Duration::from_secs(0))
+        .await
+        .is_err());
+    assert!(tokio::time::timeout(
+        Duration::from_millis(300),
+        publisher.wait_for_subscribers(1)
+    )
+    .await
+    .is_err());
+
+    let _subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        publisher.wait_for_subscribers_with_interval(1, Duration::from_millis(10)),
+    )
+    .await
+    .expect("Subscriber never connected")
+    .unwrap();
+    assert!(publisher.subscriber_count() >= 1);
+
+    rosrust::shutdown();
+    assert!(publisher.wait_for_subscribers(2).await.is_err());
+}
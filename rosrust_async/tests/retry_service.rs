@@ -0,0 +1,41 @@
+use rosrust_async::RetryService;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+
+mod util;
+
+fn call() -> tokio::task::JoinHandle<Result<TriggerRes, String>> {
+    tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", None).unwrap();
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn retry_service_test() {
+    let _roscore = util::run_roscore_for(util::Feature::RetryServiceTest);
+    rosrust::init("retry_service_test");
+
+    let mut service = RetryService::<Trigger>::new("/trigger").unwrap();
+
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    assert_eq!(response.await.unwrap().unwrap().message, "done");
+
+    // A handle lost to a panicking task is answered by its guard.
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    let task = tokio::spawn(async move {
+        let _handle = handle;
+        panic!("Handler crashed");
+    });
+    assert!(task.await.is_err());
+    assert_eq!(
+        response.await.unwrap().unwrap_err(),
+        "Request was dropped without a response"
+    );
+}
@@ -0,0 +1,35 @@
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+
+mod util;
+
+fn call(topic: &'static str) -> tokio::task::JoinHandle<Result<TriggerRes, String>> {
+    tokio::task::spawn_blocking(move || {
+        rosrust::wait_for_service(topic, None).unwrap();
+        let client = rosrust::client::<Trigger>(topic).unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn service_caller_filter_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ServiceCallerFilterTest);
+    rosrust::init("service_caller_filter_test");
+
+    let mut allowed = Service::<Trigger>::new("/allowed")
+        .unwrap()
+        .with_caller_filter(|caller| caller == rosrust::name());
+    let response = call("/allowed");
+    let handle = allowed.next_request().await.expect("Service stopped early");
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    assert_eq!(response.await.unwrap().unwrap().message, "done");
+
+    let _denied = Service::<Trigger>::new("/denied")
+        .unwrap()
+        .with_caller_filter(|caller| caller != rosrust::name());
+    let err = call("/denied").await.unwrap().unwrap_err();
+    assert!(err.contains("not authorized"));
+}
@@ -0,0 +1,36 @@
+use futures::StreamExt;
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn service_close_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ServiceCloseTest);
+    rosrust::init("service_close_test");
+
+    let service = Service::<Trigger>::new("/trigger").unwrap();
+    let call = tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", None).unwrap();
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    });
+    // Give the request time to arrive before closing.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let mut pending = service.close();
+    let handle = pending.next().await.expect("Pending request was lost");
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    let response = call.await.unwrap().unwrap();
+    assert_eq!(response.message, "done");
+    assert!(pending.next().await.is_none());
+
+    let closed = tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", Some(Duration::from_millis(200)))
+    });
+    assert!(closed.await.unwrap().is_err());
+}
@@ -0,0 +1,63 @@
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::Duration;
+
+mod util;
+
+fn call(topic: &'static str) -> tokio::task::JoinHandle<Result<TriggerRes, String>> {
+    tokio::task::spawn_blocking(move || {
+        rosrust::wait_for_service(topic, None).unwrap();
+        let client = rosrust::client::<Trigger>(topic).unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn service_health_endpoint_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ServiceHealthEndpointTest);
+    rosrust::init("service_health_endpoint_test");
+
+    let mut service = Service::<Trigger>::new("/trigger").unwrap();
+    let endpoint = service.health_endpoint().unwrap();
+
+    let health = call("/trigger/_health").await.unwrap().unwrap();
+    assert!(health.success);
+    assert_eq!(
+        health.message,
+        "requests: 0, error rate: 0.000, mean latency: 0.000 ms"
+    );
+
+    let response = call("/trigger");
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    response.await.unwrap().unwrap();
+
+    let response = call("/trigger");
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_err("failed").await;
+    response.await.unwrap().unwrap_err();
+
+    let health = call("/trigger/_health").await.unwrap().unwrap();
+    assert!(health
+        .message
+        .starts_with("requests: 2, error rate: 0.500, mean latency: "));
+
+    // The health service goes away along with the endpoint.
+    drop(endpoint);
+    let gone = tokio::task::spawn_blocking(|| {
+        (0..50).any(|_| {
+            let timeout = Some(Duration::from_millis(100));
+            let gone = rosrust::wait_for_service("/trigger/_health", timeout).is_err();
+            if !gone {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            gone
+        })
+    })
+    .await
+    .unwrap();
+    assert!(gone);
+}
@@ -0,0 +1,46 @@
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn service_next_request_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ServiceNextRequestTest);
+    rosrust::init("service_next_request_test");
+
+    let mut service = Service::<Trigger>::new("/trigger").unwrap();
+    assert!(service
+        .next_request_timeout(Duration::from_millis(200))
+        .await
+        .is_err());
+
+    let call = tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", None).unwrap();
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    });
+
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    let response = call.await.unwrap().unwrap();
+    assert!(response.success);
+    assert_eq!(response.message, "done");
+
+    let call = tokio::task::spawn_blocking(|| {
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    });
+    drop(service.next_request().await.expect("Service stopped early"));
+    let response = call.await.unwrap();
+    assert_eq!(
+        response.unwrap_err(),
+        "Request was dropped without a response"
+    );
+
+    rosrust::shutdown();
+    assert!(service.next_request().await.is_none());
+}
@@ -0,0 +1,51 @@
+use futures::StreamExt;
+use rosrust_async::{ConnectionEvent, Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriber_connection_events_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SubscriberConnectionEventsTest);
+    rosrust::init("subscriber_connection_events_test");
+
+    let subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    let mut events = subscriber.connection_events();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), events.next())
+            .await
+            .is_err()
+    );
+
+    let publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(10), events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    let publisher_uri = match event {
+        ConnectionEvent::Connected { publisher_uri } => publisher_uri,
+        event => panic!("Expected a connection, got {:?}", event),
+    };
+    assert_eq!(subscriber.publisher_uris(), vec![publisher_uri.clone()]);
+
+    // Publishers that were already connected are reported to new streams too.
+    let mut late_events = subscriber.connection_events();
+    let event = tokio::time::timeout(Duration::from_secs(10), late_events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event,
+        ConnectionEvent::Connected {
+            publisher_uri: publisher_uri.clone()
+        }
+    );
+
+    drop(publisher);
+    let event = tokio::time::timeout(Duration::from_secs(10), events.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event, ConnectionEvent::Disconnected { publisher_uri });
+}
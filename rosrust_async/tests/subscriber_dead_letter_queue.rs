@@ -0,0 +1,61 @@
+use rosrust::{RawMessage, RawMessageDescription, RosMsg};
+use rosrust_async::Subscriber;
+use rosrust_msg::std_msgs::{Int32, String as RosString};
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriber_dead_letter_queue_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SubscriberDeadLetterQueueTest);
+    rosrust::init("subscriber_dead_letter_queue_test");
+
+    assert!(Subscriber::<RosString>::with_dead_letter_queue("chatter", 10, 0).is_err());
+    let (mut subscriber, mut dead_letters) =
+        Subscriber::<RosString>::with_dead_letter_queue("chatter", 10, 10).unwrap();
+    // Claims to be a `String`, so the subscriber accepts the connection, but sends any bytes.
+    let publisher = rosrust::publish_with_description::<RawMessage>(
+        "chatter",
+        10,
+        RawMessageDescription::from_message::<RosString>(),
+    )
+    .unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // A length of 1000 for a string that never arrives.
+    let malformed = 1000u32.to_le_bytes().to_vec();
+    publisher.send(RawMessage(malformed.clone())).unwrap();
+    let raw = tokio::time::timeout(Duration::from_secs(10), dead_letters.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(raw, malformed);
+    assert_eq!(subscriber.latest(), None);
+
+    let msg = RosString {
+        data: "hello".into(),
+    };
+    let mut body = vec![];
+    msg.encode(&mut body).unwrap();
+    publisher.send(RawMessage(body)).unwrap();
+    let received = tokio::time::timeout(Duration::from_secs(10), subscriber.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received, msg);
+
+    // Publishers of other types are rejected when connecting, instead of producing dead letters.
+    let (_other, mut other_dead_letters) =
+        Subscriber::<RosString>::with_dead_letter_queue("other", 10, 10).unwrap();
+    let int_publisher = rosrust::publish::<Int32>("other", 10).unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(int_publisher.subscriber_count(), 0);
+    int_publisher.send(Int32 { data: 1000 }).unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_secs(1), other_dead_letters.recv())
+            .await
+            .is_err()
+    );
+}
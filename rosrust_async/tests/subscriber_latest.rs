@@ -0,0 +1,26 @@
+use rosrust_async::{Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriber_latest_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SubscriberLatestTest);
+    rosrust::init("subscriber_latest_test");
+
+    let mut subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    let mut publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(subscriber.latest(), None);
+
+    for data in 0..5 {
+        publisher.send(Int32 { data }).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    assert_eq!(subscriber.latest(), Some(Int32 { data: 4 }));
+    assert_eq!(subscriber.latest(), None);
+}
@@ -0,0 +1,32 @@
+use rosrust_async::{Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::{Duration, Instant};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriber_recv_timeout_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SubscriberRecvTimeoutTest);
+    rosrust::init("subscriber_recv_timeout_test");
+
+    let mut subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    let mut publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    while publisher.subscriber_count() == 0 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let start = Instant::now();
+    assert!(subscriber
+        .recv_timeout(Duration::from_millis(300))
+        .await
+        .is_err());
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    publisher.send(Int32 { data: 42 }).await.unwrap();
+    let message = subscriber
+        .recv_timeout(Duration::from_secs(10))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.data, 42);
+}
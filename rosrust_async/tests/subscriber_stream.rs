@@ -0,0 +1,38 @@
+use futures::StreamExt;
+use rosrust_async::{Publisher, Subscriber};
+use rosrust_msg::std_msgs::Int32;
+use std::time::Duration;
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscriber_stream_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SubscriberStreamTest);
+    rosrust::init("subscriber_stream_test");
+
+    let subscriber = Subscriber::<Int32>::new("chatter", 10).unwrap();
+    let mut publisher = Publisher::<Int32>::new("chatter", 10).unwrap();
+    publisher.wait_for_subscribers(1).await.unwrap();
+
+    // The stream keeps the subscription alive, and can be moved to another task.
+    let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+    let forward = tokio::spawn(
+        subscriber
+            .into_stream()
+            .map(|message| message.data)
+            .take(5)
+            .for_each(move |data| {
+                let tx = tx.clone();
+                async move { tx.send(data).await.unwrap() }
+            }),
+    );
+
+    for data in 0..5 {
+        publisher.send(Int32 { data }).await.unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .unwrap();
+        assert_eq!(received, Some(data));
+    }
+    forward.await.unwrap();
+}
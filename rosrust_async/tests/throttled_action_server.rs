@@ -0,0 +1,33 @@
+use rosrust_async::{ActionClient, ThrottledActionServer};
+use rosrust_msg::actionlib_tutorials::{
+    FibonacciAction, FibonacciFeedback, FibonacciGoal, FibonacciResult,
+};
+
+mod util;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn throttled_action_server_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ThrottledActionServerTest);
+    rosrust::init("throttled_action_server_test");
+
+    let mut server = ThrottledActionServer::<FibonacciAction>::new("fibonacci", 1.0).unwrap();
+    let client = ActionClient::<FibonacciAction>::new("fibonacci")
+        .await
+        .unwrap();
+    let mut goal = client.send_goal(FibonacciGoal { order: 10 }).await.unwrap();
+
+    let handle = server.recv().await;
+    for i in 0..10 {
+        handle
+            .publish_feedback(FibonacciFeedback { sequence: vec![i] })
+            .await
+            .unwrap();
+    }
+    assert_eq!(server.dropped_feedback(), 9);
+
+    handle
+        .response_builder()
+        .result(FibonacciResult { sequence: vec![] })
+        .send_succeeded();
+    goal.wait_for_result().await.unwrap();
+}
@@ -0,0 +1,46 @@
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::Duration;
+
+mod util;
+
+fn call() -> tokio::task::JoinHandle<Result<TriggerRes, String>> {
+    tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", None).unwrap();
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timeout_service_test() {
+    let _roscore = util::run_roscore_for(util::Feature::TimeoutServiceTest);
+    rosrust::init("timeout_service_test");
+
+    let mut service = Service::<Trigger>::new("/trigger")
+        .unwrap()
+        .with_request_timeout(Duration::from_millis(500));
+
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert!(!handle.timed_out());
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    assert_eq!(response.await.unwrap().unwrap().message, "done");
+
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(handle.timed_out());
+    assert_eq!(
+        response.await.unwrap().unwrap_err(),
+        "Request timed out without a response"
+    );
+    // Responding after the timeout does nothing.
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "late".into(),
+    });
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "opentelemetry")]
+
+use rosrust_async::Service;
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+
+mod util;
+
+fn call() -> tokio::task::JoinHandle<Result<TriggerRes, String>> {
+    tokio::task::spawn_blocking(|| {
+        rosrust::wait_for_service("/trigger", None).unwrap();
+        let client = rosrust::client::<Trigger>("/trigger").unwrap();
+        client.req(&TriggerReq {}).unwrap()
+    })
+}
+
+// No tracer provider is installed, so the spans go to the no-op global tracer. This checks that
+// tracing doesn't get in the way of the requests and responses.
+#[tokio::test(flavor = "multi_thread")]
+async fn tracing_service_test() {
+    let _roscore = util::run_roscore_for(util::Feature::TracingServiceTest);
+    rosrust::init("tracing_service_test");
+
+    let mut service = Service::<Trigger>::new("/trigger").unwrap().with_tracing();
+
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    assert_eq!(handle.request(), &TriggerReq {});
+    handle.send_ok(TriggerRes {
+        success: true,
+        message: "done".into(),
+    });
+    assert_eq!(response.await.unwrap().unwrap().message, "done");
+
+    let response = call();
+    let handle = service.next_request().await.expect("Service stopped early");
+    handle.send_err("failed").await;
+    assert_eq!(response.await.unwrap().unwrap_err(), "failed");
+
+    let response = call();
+    drop(service.next_request().await.expect("Service stopped early"));
+    assert_eq!(
+        response.await.unwrap().unwrap_err(),
+        "Request was dropped without a response"
+    );
+}
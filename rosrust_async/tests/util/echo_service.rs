@@ -0,0 +1,91 @@
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use rosrust::{Message, RosMsg, ServicePair};
+use rosrust_async::HasIdempotencyKey;
+use std::io;
+
+// A hand written service, since generated requests can't be used as cache keys.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Echo;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EchoReq {
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EchoRes {
+    pub data: String,
+}
+
+impl Message for Echo {
+    fn msg_definition() -> String {
+        String::new()
+    }
+
+    fn md5sum() -> String {
+        "d41d8cd98f00b204e9800998ecf8427e".into()
+    }
+
+    fn msg_type() -> String {
+        "rosrust_async_test/Echo".into()
+    }
+}
+
+impl RosMsg for Echo {
+    fn encode<W: io::Write>(&self, _w: W) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn decode<R: io::Read>(_r: R) -> io::Result<Self> {
+        Ok(Echo)
+    }
+}
+
+impl ServicePair for Echo {
+    type Request = EchoReq;
+    type Response = EchoRes;
+}
+
+impl RosMsg for EchoReq {
+    fn encode<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.data.encode(w)
+    }
+
+    fn decode<R: io::Read>(r: R) -> io::Result<Self> {
+        Ok(EchoReq {
+            data: RosMsg::decode(r)?,
+        })
+    }
+}
+
+impl RosMsg for EchoRes {
+    fn encode<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.data.encode(w)
+    }
+
+    fn decode<R: io::Read>(r: R) -> io::Result<Self> {
+        Ok(EchoRes {
+            data: RosMsg::decode(r)?,
+        })
+    }
+}
+
+impl HasIdempotencyKey for EchoReq {
+    fn idempotency_key(&self) -> String {
+        self.data.clone()
+    }
+}
+
+pub fn call_echo(
+    topic: &'static str,
+    data: &str,
+) -> tokio::task::JoinHandle<Result<String, String>> {
+    let request = EchoReq { data: data.into() };
+    tokio::task::spawn_blocking(move || {
+        rosrust::wait_for_service(topic, None).unwrap();
+        let client = rosrust::client::<Echo>(topic).unwrap();
+        client.req(&request).unwrap().map(|response| response.data)
+    })
+}
@@ -0,0 +1,83 @@
+pub use child_process_terminator::ChildProcessTerminator;
+use std::env;
+use std::process::Command;
+use std::thread::sleep;
+use std::time::Duration;
+
+mod child_process_terminator;
+pub mod echo_service;
+
+fn rostopic_listing_succeeds() -> bool {
+    Command::new("rostopic")
+        .arg("list")
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
+
+fn await_roscore() {
+    while !rostopic_listing_succeeds() {
+        sleep(Duration::from_millis(100));
+    }
+}
+
+fn run_roscore(port: u32) -> ChildProcessTerminator {
+    env::set_var("ROS_MASTER_URI", format!("http://localhost:{}", port));
+    let roscore =
+        ChildProcessTerminator::spawn(Command::new("roscore").arg("-p").arg(format!("{}", port)));
+    await_roscore();
+    roscore
+}
+
+pub fn run_roscore_for(feature: Feature) -> ChildProcessTerminator {
+    run_roscore(generate_port(feature))
+}
+
+#[allow(dead_code, clippy::enum_variant_names)]
+#[repr(u32)]
+pub enum Feature {
+    PublisherSelectTest = 1,
+    PublisherSinkTest = 2,
+    ServiceNextRequestTest = 3,
+    ClientDiscoveryTest = 4,
+    ClientReqTimeoutTest = 5,
+    SubscriberRecvTimeoutTest = 6,
+    SubscriberLatestTest = 7,
+    ServiceCallerFilterTest = 8,
+    PublisherWaitForSubscribersTest = 9,
+    ParamTest = 10,
+    ServiceCloseTest = 11,
+    ThrottledActionServerTest = 12,
+    InlinePublisherTest = 13,
+    SubscriberDeadLetterQueueTest = 14,
+    BudgetedActionServerTest = 15,
+    ClientOptionsTest = 16,
+    ActionGoalStreamTest = 17,
+    CachingActionServerTest = 18,
+    Lz4CompressedPublisherTest = 19,
+    ZstdCompressedPublisherTest = 20,
+    ConcurrencyLimitedClientTest = 21,
+    ValidatingPublisherTest = 22,
+    IdempotentServiceTest = 23,
+    CachedServiceTest = 24,
+    PublisherSendWithDeadlineTest = 25,
+    SubscriberConnectionEventsTest = 26,
+    ActionServerCancelAllGoalsTest = 27,
+    PublisherSendWithHeaderTest = 28,
+    TracingServiceTest = 29,
+    LatchedSubscriberTest = 30,
+    TimeoutServiceTest = 31,
+    HookedClientTest = 32,
+    ActionServerStatsTest = 33,
+    PublisherSendWithConfirmationTest = 34,
+    ServiceHealthEndpointTest = 35,
+    SubscriberStreamTest = 36,
+    PublisherSendRawBytesTest = 37,
+    RetryServiceTest = 38,
+    PooledClientTest = 39,
+}
+
+fn generate_port(feature: Feature) -> u32 {
+    15000 + feature as u32
+}
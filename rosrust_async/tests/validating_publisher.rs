@@ -0,0 +1,73 @@
+use rosrust::{Message, RosMsg};
+use rosrust_async::{Publisher, ValidatedSendError};
+use serde_derive::Serialize;
+use std::io;
+
+mod util;
+
+// Hand-written, so its `Serialize` implementation can disagree with its definition.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct Reading {
+    id: u32,
+    values: Vec<f64>,
+}
+
+impl RosMsg for Reading {
+    fn encode<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        self.id.encode(&mut w)?;
+        for value in &self.values {
+            value.encode(&mut w)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: io::Read>(mut r: R) -> io::Result<Self> {
+        let id = RosMsg::decode(&mut r)?;
+        let values = (0..3)
+            .map(|_| RosMsg::decode(&mut r))
+            .collect::<io::Result<_>>()?;
+        Ok(Reading { id, values })
+    }
+}
+
+impl Message for Reading {
+    fn msg_definition() -> String {
+        "uint32 id\nfloat64[3] values\n".into()
+    }
+
+    fn md5sum() -> String {
+        "*".into()
+    }
+
+    fn msg_type() -> String {
+        "test_msgs/Reading".into()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validating_publisher_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ValidatingPublisherTest);
+    rosrust::init("validating_publisher_test");
+
+    let mut publisher = Publisher::<Reading>::new("readings", 10)
+        .unwrap()
+        .with_schema_validation()
+        .unwrap();
+
+    let valid = Reading {
+        id: 1,
+        values: vec![1.0, 2.0, 3.0],
+    };
+    assert!(publisher.validate(&valid).is_ok());
+    assert!(publisher.send(valid).await.is_ok());
+
+    let too_short = Reading {
+        id: 2,
+        values: vec![1.0, 2.0],
+    };
+    assert!(publisher.validate(&too_short).is_err());
+    assert!(matches!(
+        publisher.send(too_short).await,
+        Err(ValidatedSendError::Schema(_)),
+    ));
+}
@@ -102,7 +102,7 @@ impl Msg {
 
                 fn try_from(src: #crate_prefix MsgValue) -> Result<Self, ()> {
                     use std::convert::TryInto;
-                    let message: #crate_prefix MsgMessage = src.try_into()?;
+                    let message: #crate_prefix MsgMessage = src.try_into().map_err(|_| ())?;
                     message.try_into()
                 }
             }
@@ -206,6 +206,10 @@ impl Msg {
                     self.header.stamp = clock.now();
                 }
             }
+
+            fn header_stamp(&self) -> Option<#crate_prefix Time> {
+                Some(self.header.stamp)
+            }
         }
     }
 }
@@ -299,7 +303,7 @@ fn field_info_field_from_value_token_stream<T: ToTokens>(
     let name_str = field_info.name();
     match field_info.case() {
         FieldCase::Unit | FieldCase::Vector | FieldCase::Array(_) => {
-            quote! { #name: src.remove(#name_str).ok_or(())?.try_into()?, }
+            quote! { #name: src.remove(#name_str).ok_or(())?.try_into().map_err(|_| ())?, }
         }
         FieldCase::Const(_) => quote! {},
     }
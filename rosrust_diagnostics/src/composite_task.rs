@@ -9,13 +9,43 @@ use crate::{Level, Status, Task};
 /// For instance, this could be used to combine the calibration and offset data
 /// from an IMU driver.
 ///
+/// By default every child writes into the same status, as with the `run_diagnostics!` macro.
+/// Setting an `AggregateStrategy` instead runs every child with its own status, and prefixes
+/// the keys of its values with its name.
+///
 /// This is an easy way of combining task, but it performs heap allocations and
 /// takes ownership of tasks. To maintain ownership, and manage things optimally
 /// implement your own `Task`, by utilizing the `run_diagnostics!` macro for
 /// similar functionality.
 pub struct CompositeTask<'a> {
     name: String,
-    tasks: Vec<&'a dyn Task>,
+    tasks: Vec<Child<'a>>,
+    strategy: Option<AggregateStrategy>,
+}
+
+/// Determines the summary message of a `CompositeTask` with isolated children.
+///
+/// The level is always the worst level among the children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateStrategy {
+    /// Only the messages of the children with the worst level are listed.
+    Worst,
+    /// The messages of all children are listed.
+    All,
+}
+
+enum Child<'a> {
+    Borrowed(&'a dyn Task),
+    Owned(Box<dyn Task>),
+}
+
+impl<'a> Child<'a> {
+    fn task(&self) -> &dyn Task {
+        match self {
+            Child::Borrowed(task) => *task,
+            Child::Owned(task) => &**task,
+        }
+    }
 }
 
 impl<'a> CompositeTask<'a> {
@@ -24,6 +54,15 @@ impl<'a> CompositeTask<'a> {
         Self {
             name: name.to_string(),
             tasks: vec![],
+            strategy: None,
+        }
+    }
+
+    /// Creates a new composite task that runs every child with its own status.
+    pub fn with_strategy(name: impl std::string::ToString, strategy: AggregateStrategy) -> Self {
+        Self {
+            strategy: Some(strategy),
+            ..Self::new(name)
         }
     }
 
@@ -31,8 +70,48 @@ impl<'a> CompositeTask<'a> {
     ///
     /// This child will be called every time the composit task is called.
     pub fn add_task(&mut self, task: &'a dyn Task) {
-        self.tasks.push(task)
+        self.tasks.push(Child::Borrowed(task))
     }
+
+    /// Adds a child to the composite task, taking ownership of it.
+    ///
+    /// This child will be called every time the composit task is called.
+    pub fn add<T: Task + 'static>(&mut self, task: T) {
+        self.tasks.push(Child::Owned(Box::new(task)))
+    }
+
+    fn run_isolated(&self, status: &mut Status, strategy: AggregateStrategy) {
+        let (level, summaries) = run_isolated_children(self.tasks.iter().map(Child::task), status);
+        let message = summaries
+            .into_iter()
+            .filter(|(child_level, _)| strategy == AggregateStrategy::All || *child_level == level)
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>()
+            .join("; ");
+        status.set_summary(level, message);
+    }
+}
+
+/// Runs every task with its own status, adding its values to `status` prefixed with its name.
+///
+/// Returns the worst level among the tasks, along with the level and the prefixed message
+/// of every task, in order.
+pub(crate) fn run_isolated_children<'t>(
+    tasks: impl IntoIterator<Item = &'t dyn Task>,
+    status: &mut Status,
+) -> (Level, Vec<(Level, String)>) {
+    let mut level = Level::Ok;
+    let mut summaries = vec![];
+    for task in tasks {
+        let mut child = Status::default();
+        task.run(&mut child);
+        for value in child.values {
+            status.add(format!("{}: {}", task.name(), value.key), value.value);
+        }
+        level = level | child.level;
+        summaries.push((child.level, format!("{}: {}", task.name(), child.message)));
+    }
+    (level, summaries)
 }
 
 impl<'a> Task for CompositeTask<'a> {
@@ -42,10 +121,15 @@ impl<'a> Task for CompositeTask<'a> {
     }
 
     fn run(&self, status: &mut Status) {
+        if let Some(strategy) = self.strategy {
+            self.run_isolated(status, strategy);
+            return;
+        }
+
         let mut runner = CompositeTaskRunner::new(status);
 
         for task in &self.tasks {
-            runner.run(&(**task));
+            runner.run(task.task());
         }
     }
 }
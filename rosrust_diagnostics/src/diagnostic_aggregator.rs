@@ -0,0 +1,76 @@
+use crate::composite_task::run_isolated_children;
+use crate::{Level, Status, Task, TaskExt};
+
+/// Aggregates the statuses of multiple diagnostic tasks into a single status.
+///
+/// Every child task is run with its own status. The aggregated status has the worst
+/// level among the children, and a message listing the children that are not OK.
+/// The values of every child are kept, prefixed with the child's name.
+///
+/// Since the aggregator is a task itself, aggregators can be nested to build
+/// hierarchical diagnostic trees, for instance grouping all the sensors of a robot.
+///
+/// This is similar to a `CompositeTask` with an `AggregateStrategy`, but only lists the
+/// children that are not OK, and can be sent between threads.
+pub struct DiagnosticAggregator {
+    name: String,
+    tasks: Vec<Box<dyn Task + Send + Sync>>,
+}
+
+impl DiagnosticAggregator {
+    /// Creates a new aggregator with the given name.
+    pub fn new(name: impl std::string::ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            tasks: vec![],
+        }
+    }
+
+    /// Adds a child to the aggregator.
+    ///
+    /// This child will be run every time the aggregator is run.
+    pub fn add_task(&mut self, task: impl TaskExt) {
+        self.tasks.push(task.boxed())
+    }
+
+    /// Adds a child to the aggregator, returning the aggregator.
+    pub fn with_task(mut self, task: impl TaskExt) -> Self {
+        self.add_task(task);
+        self
+    }
+
+    /// Returns the number of children in the aggregator.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns whether the aggregator has no children.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl Task for DiagnosticAggregator {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let tasks = self.tasks.iter().map(|task| &**task as &dyn Task);
+        let (level, summaries) = run_isolated_children(tasks, status);
+        let problems = summaries
+            .into_iter()
+            .filter(|(child_level, _)| *child_level != Level::Ok)
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>();
+
+        if problems.is_empty() {
+            status.set_summary(level, "All sub-tasks are OK.");
+        } else {
+            status.set_summary(level, problems.join("; "));
+        }
+    }
+}
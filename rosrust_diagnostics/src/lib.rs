@@ -8,27 +8,40 @@ support ownership models with the minimum amounts of allocation and dynamic inte
 */
 #![deny(missing_docs)]
 
-pub use composite_task::{CompositeTask, CompositeTaskRunner};
+pub use composite_task::{AggregateStrategy, CompositeTask, CompositeTaskRunner};
+pub use diagnostic_aggregator::DiagnosticAggregator;
 pub use function_task::{FunctionExt, FunctionTask};
+pub use plugins::{force_update, init, register_task_plugin, update};
 pub use rosrust_msg::diagnostic_msgs::{DiagnosticArray, DiagnosticStatus, KeyValue};
 pub use rosrust_msg::std_msgs::Header;
 pub use status::Status;
-pub use task::Task;
-pub use tasks::{FrequencyStatus, Heartbeat, TimestampStatus};
+pub use task::{Task, TaskExt};
+pub use tasks::{
+    BandwidthStatus, ChildProcessStatus, DiskUsageStatus, EnvVarStatus, FileExistsStatus,
+    FrequencyStatus, GracefulDegradationStatus, Heartbeat, NetworkInterfaceStatus, OperationalMode,
+    PublisherStatus, ResourceLimitStatus, ResourceLimits, ServiceCallStatus, SystemTimeStatus,
+    TcpConnectionStatus, TimestampStatus, TopicBandwidthStatus, TopicDiagnostic, TopicEchoStatus,
+    VersionStatus, Watchdog,
+};
 pub use updater::{Updater, UpdaterLowLevelExt};
 
 mod composite_task;
+mod diagnostic_aggregator;
 mod function_task;
 #[macro_use]
 mod macros;
+mod plugins;
 mod status;
 mod task;
 pub mod tasks;
 mod updater;
 
 /// Possible levels of operations in a diagnostic status.
+///
+/// More levels may be added in the future, so matches on a level need a wildcard arm.
 #[repr(i8)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Level {
     /// The diagnostic concluded that all checks passed.
     Ok = DiagnosticStatus::OK,
@@ -36,4 +49,30 @@ pub enum Level {
     Warn = DiagnosticStatus::WARN,
     /// Checks determined an error happened.
     Error = DiagnosticStatus::ERROR,
+    /// No recent data was available to perform the checks.
+    Stale = DiagnosticStatus::STALE,
+}
+
+impl Level {
+    /// Returns the more severe of the two levels.
+    ///
+    /// Levels are ordered by severity as `Ok < Warn < Error < Stale`.
+    #[inline]
+    pub fn max(self, other: Level) -> Level {
+        if other as i8 > self as i8 {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl std::ops::BitOr for Level {
+    type Output = Level;
+
+    /// Same as `Level::max`.
+    #[inline]
+    fn bitor(self, other: Level) -> Level {
+        self.max(other)
+    }
 }
@@ -0,0 +1,81 @@
+use crate::{Task, Updater};
+use lazy_static::lazy_static;
+use rosrust::error::Result;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref UPDATER: Mutex<Option<Updater<'static>>> = Mutex::new(None);
+    static ref PLUGINS: Mutex<Vec<Box<dyn Task + Send + Sync>>> = Mutex::new(vec![]);
+}
+
+/// Initializes the global updater, which runs every task registered as a plugin.
+///
+/// Tasks registered before the initialization are advertised at this point.
+///
+/// The call will fail if creating a publisher for diagnostics fails, which should only
+/// happen if `rosrust::init()` was not called already.
+pub fn init() -> Result<()> {
+    let updater = Updater::new()?;
+    let mut global_updater = UPDATER.lock().expect(FAILED_TO_LOCK);
+    let plugins = PLUGINS.lock().expect(FAILED_TO_LOCK);
+    for task in plugins.iter() {
+        updater.advertise_added_task(&**task)?;
+    }
+    *global_updater = Some(updater);
+    Ok(())
+}
+
+/// Registers a task to be run by the global updater.
+///
+/// This allows library crates to provide their own diagnostics, without the node having to
+/// add every one of them to its updater.
+///
+/// Tasks can be registered before the global updater is initialized. Otherwise the task is
+/// advertised right away, and the call only fails if the advertisement fails. Note that the
+/// task is still registered, as the advertisement failing isn't catastrophic.
+///
+/// Tasks must not register other tasks while they run, as that would deadlock.
+pub fn register_task_plugin(task: Box<dyn Task + Send + Sync>) -> Result<()> {
+    let updater = UPDATER.lock().expect(FAILED_TO_LOCK);
+    let mut plugins = PLUGINS.lock().expect(FAILED_TO_LOCK);
+    let advertisement_result = match &*updater {
+        Some(updater) => updater.advertise_added_task(&*task),
+        None => Ok(()),
+    };
+    plugins.push(task);
+    advertisement_result
+}
+
+/// Causes the global updater to run the plugins if the inter-update interval has been exceeded.
+///
+/// Fails if the global updater was not initialized.
+pub fn update() -> Result<()> {
+    with_plugins(|updater, plugins| updater.update_with_extra(plugins))
+}
+
+/// Forces the global updater to run the plugins.
+///
+/// Fails if the global updater was not initialized.
+pub fn force_update() -> Result<()> {
+    with_plugins(|updater, plugins| updater.force_update_with_extra(plugins))
+}
+
+fn with_plugins<F>(handler: F) -> Result<()>
+where
+    F: FnOnce(&Updater<'static>, &[&dyn Task]) -> Result<()>,
+{
+    let updater = UPDATER.lock().expect(FAILED_TO_LOCK);
+    let updater = match &*updater {
+        Some(updater) => updater,
+        None => return Err(NOT_INITIALIZED.into()),
+    };
+    let plugins = PLUGINS.lock().expect(FAILED_TO_LOCK);
+    let plugins = plugins
+        .iter()
+        .map(|task| &**task as &dyn Task)
+        .collect::<Vec<_>>();
+    handler(updater, &plugins)
+}
+
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
+static NOT_INITIALIZED: &str = "Global diagnostics updater was not initialized";
@@ -88,9 +88,7 @@ impl Status {
             (_, Level::Ok) => {}
             _ => self.merge_messages(message),
         }
-        if level as i8 > self.level as i8 {
-            self.level = level;
-        }
+        self.level = self.level | level;
     }
 
     /// Merges the passed in status with this status by merging the level and message.
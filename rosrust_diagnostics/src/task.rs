@@ -1,4 +1,5 @@
 use crate::Status;
+use std::sync::Arc;
 
 /// Trait that describes any diagnostic task.
 pub trait Task: Sync {
@@ -12,3 +13,35 @@ pub trait Task: Sync {
     /// Runs this diagnostic task, and outputs the result into the provided status.
     fn run(&self, status: &mut Status);
 }
+
+/// Shared tasks can be used directly, for example when a subscriber callback needs to
+/// update the same `FrequencyStatus` that the `Updater` runs.
+impl<T: Task + Send + ?Sized> Task for Arc<T> {
+    #[inline]
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    #[inline]
+    fn run(&self, status: &mut Status) {
+        (**self).run(status)
+    }
+}
+
+/// Extension trait for tasks that allows them to be used as trait objects easily.
+///
+/// This is implemented for every task that can be sent between threads.
+pub trait TaskExt: Task + Send + Sync + Sized + 'static {
+    /// Moves the task into a `Box`, for storing it among tasks of other types.
+    fn boxed(self) -> Box<dyn Task + Send + Sync>;
+}
+
+impl<T> TaskExt for T
+where
+    T: Task + Send + Sync + 'static,
+{
+    #[inline]
+    fn boxed(self) -> Box<dyn Task + Send + Sync> {
+        Box::new(self)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{Level, Status, Task};
+use rosrust::Time;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// The structure for building a bandwidth status task.
+///
+/// Use `BandwidthStatus::builder()` to create an instance of this structure.
+pub struct BandwidthStatusBuilder<'a> {
+    min_bytes_per_sec: f64,
+    max_bytes_per_sec: f64,
+    tolerance: f64,
+    window_size: usize,
+    name: &'a str,
+}
+
+impl<'a> BandwidthStatusBuilder<'a> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            min_bytes_per_sec: 0.0,
+            max_bytes_per_sec: f64::INFINITY,
+            tolerance: 0.1,
+            window_size: 5,
+            name: "Bandwidth Status",
+        }
+    }
+
+    /// Sets the minimum bandwidth that is expected, in bytes per second.
+    ///
+    /// Defaults to zero.
+    #[inline]
+    pub fn min_bytes_per_sec(&mut self, value: f64) -> &mut Self {
+        self.min_bytes_per_sec = value;
+        self
+    }
+
+    /// Sets the maximum bandwidth that is expected, in bytes per second.
+    ///
+    /// Defaults to infinity.
+    #[inline]
+    pub fn max_bytes_per_sec(&mut self, value: f64) -> &mut Self {
+        self.max_bytes_per_sec = value;
+        self
+    }
+
+    /// Sets the tolerance to how far out of bounds a bandwidth is allowed to go.
+    ///
+    /// Defaults to `0.1`.
+    ///
+    /// It is provided as a fraction of the bandwidth limits, the same way as
+    /// `FrequencyStatusBuilder::tolerance`.
+    #[inline]
+    pub fn tolerance(&mut self, value: f64) -> &mut Self {
+        self.tolerance = value;
+        self
+    }
+
+    /// Sets the number of runs we're averaging while estimating the bandwidth.
+    ///
+    /// Defaults to `5` runs.
+    #[inline]
+    pub fn window_size(&mut self, value: usize) -> &mut Self {
+        self.window_size = value;
+        self
+    }
+
+    /// Sets the name of the task.
+    ///
+    /// Defaults to "Bandwidth Status".
+    #[inline]
+    pub fn name(&mut self, name: &'a str) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Builds the bandwidth status with the provided parameters.
+    #[inline]
+    pub fn build(&self) -> BandwidthStatus {
+        BandwidthStatus::new(
+            self.min_bytes_per_sec,
+            self.max_bytes_per_sec,
+            self.tolerance,
+            self.window_size,
+            self.name.into(),
+        )
+    }
+}
+
+/// Diagnostic task that monitors the bandwidth of a stream of data.
+///
+/// This diagnostic task sums up the byte counts passed to its tick method, and creates
+/// corresponding diagnostics. It will report a warning if the bandwidth is outside acceptable
+/// bounds, and report an error if no bytes were recorded in the latest window.
+///
+/// To measure a topic without handling the messages yourself, use `TopicBandwidthStatus`.
+pub struct BandwidthStatus {
+    min_bytes_per_sec: f64,
+    max_bytes_per_sec: f64,
+    min_tolerated_bytes_per_sec: f64,
+    max_tolerated_bytes_per_sec: f64,
+    name: String,
+    tracker: Mutex<Tracker>,
+}
+
+struct Tracker {
+    bytes: usize,
+    history: VecDeque<HistoryEntry>,
+    window_size: usize,
+}
+
+impl Tracker {
+    #[inline]
+    fn new(window_size: usize) -> Tracker {
+        let mut tracker = Tracker {
+            bytes: 0,
+            history: VecDeque::with_capacity(window_size),
+            window_size,
+        };
+
+        tracker.clear();
+
+        tracker
+    }
+
+    fn clear(&mut self) {
+        self.bytes = 0;
+
+        self.history.clear();
+        let history_entry = HistoryEntry::new(0);
+
+        self.history
+            .extend((0..self.window_size).map(|_| history_entry.clone()));
+    }
+}
+
+impl BandwidthStatus {
+    /// Creates a builder for a new bandwidth status task.
+    #[inline]
+    pub fn builder<'a>() -> BandwidthStatusBuilder<'a> {
+        BandwidthStatusBuilder::new()
+    }
+
+    /// Creates a new bandwidth status based on the provided parameters.
+    ///
+    /// Look at the `BandwidthStatusBuilder` for more information about the parameters and
+    /// reasonable defaults.
+    #[inline]
+    pub fn new(
+        min_bytes_per_sec: f64,
+        max_bytes_per_sec: f64,
+        tolerance: f64,
+        window_size: usize,
+        name: String,
+    ) -> Self {
+        Self {
+            min_bytes_per_sec,
+            max_bytes_per_sec,
+            min_tolerated_bytes_per_sec: min_bytes_per_sec * (1.0 - tolerance),
+            max_tolerated_bytes_per_sec: max_bytes_per_sec * (1.0 + tolerance),
+            name,
+            tracker: Mutex::new(Tracker::new(window_size)),
+        }
+    }
+
+    /// Signals that the given number of bytes has passed through.
+    #[inline]
+    pub fn tick(&self, bytes: usize) {
+        self.tracker.lock().expect(FAILED_TO_LOCK).bytes += bytes;
+    }
+
+    /// Resets the statistics.
+    #[inline]
+    pub fn clear(&self) {
+        self.tracker.lock().expect(FAILED_TO_LOCK).clear();
+    }
+
+    fn bandwidth_to_summary(&self, bandwidth: f64) -> (Level, &str) {
+        if bandwidth <= 0.0 {
+            (Level::Error, "No bytes recorded.")
+        } else if bandwidth < self.min_tolerated_bytes_per_sec {
+            (Level::Warn, "Bandwidth too low.")
+        } else if bandwidth > self.max_tolerated_bytes_per_sec {
+            (Level::Warn, "Bandwidth too high.")
+        } else {
+            (Level::Ok, "Desired bandwidth met")
+        }
+    }
+
+    fn add_bandwidth_info(&self, status: &mut Status) {
+        if self.min_bytes_per_sec > 0.0 {
+            status.add(
+                "Minimum acceptable bandwidth (B/s)",
+                self.min_tolerated_bytes_per_sec,
+            )
+        }
+        if self.max_bytes_per_sec != f64::INFINITY {
+            status.add(
+                "Maximum acceptable bandwidth (B/s)",
+                self.max_tolerated_bytes_per_sec,
+            )
+        }
+    }
+}
+
+impl Task for BandwidthStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let mut tracker = match self.tracker.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside bandwidth check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+        let history_end = HistoryEntry::new(tracker.bytes);
+
+        let end_bytes = history_end.bytes;
+        let end_time = history_end.time;
+
+        let history_start = match tracker.history.pop_front() {
+            Some(value) => value,
+            None => {
+                status.set_summary(
+                    Level::Error,
+                    "History in bandwidth status tracker is unexpectedly missing elements.",
+                );
+                return;
+            }
+        };
+        tracker.history.push_back(history_end);
+
+        drop(tracker);
+
+        let bytes = end_bytes - history_start.bytes;
+        let window = (end_time - history_start.time).seconds();
+        let bandwidth = if window > 0.0 {
+            bytes as f64 / window
+        } else {
+            0.0
+        };
+
+        let (level, message) = self.bandwidth_to_summary(bandwidth);
+        status.set_summary(level, message);
+
+        status.add("Bytes in window", bytes);
+        status.add("Bytes since startup", end_bytes);
+        status.add("Duration of window (s)", window);
+        status.add("Actual bandwidth (B/s)", bandwidth);
+
+        self.add_bandwidth_info(status)
+    }
+}
+
+#[derive(Clone)]
+struct HistoryEntry {
+    bytes: usize,
+    time: Time,
+}
+
+impl HistoryEntry {
+    fn new(bytes: usize) -> HistoryEntry {
+        HistoryEntry {
+            bytes,
+            time: rosrust::now(),
+        }
+    }
+}
+
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
@@ -0,0 +1,66 @@
+use crate::{Level, Status, Task};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+/// Diagnostic task that checks whether a spawned child process is still running.
+///
+/// The child is shared with the code that spawned it, so the node can keep interacting
+/// with the process while the task monitors it.
+pub struct ChildProcessStatus {
+    child: Arc<Mutex<Child>>,
+    name: String,
+}
+
+impl ChildProcessStatus {
+    /// Creates a task with the given name, monitoring the provided child process.
+    #[inline]
+    pub fn new(name: &str, child: Arc<Mutex<Child>>) -> Self {
+        Self {
+            child,
+            name: name.into(),
+        }
+    }
+
+    /// Returns the child process being monitored.
+    #[inline]
+    pub fn child(&self) -> &Arc<Mutex<Child>> {
+        &self.child
+    }
+}
+
+impl Task for ChildProcessStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let mut child = match self.child.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside child process check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        status.add("PID", child.id());
+
+        match child.try_wait() {
+            Ok(None) => status.set_summary(Level::Ok, "Process is running."),
+            Ok(Some(exit_status)) => match exit_status.code() {
+                Some(code) => {
+                    status.add("Exit code", code);
+                    status.set_summary(Level::Error, format!("Process exited with code {}.", code));
+                }
+                None => status.set_summary(Level::Error, "Process was terminated by a signal."),
+            },
+            Err(err) => status.set_summary(
+                Level::Error,
+                format!("Failed to query process state: {}", err),
+            ),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Level, Status, Task};
+use nix::sys::statvfs::statvfs;
+use std::path::{Path, PathBuf};
+
+/// Diagnostic task that monitors the space used on the file system containing a path.
+///
+/// The usage is reported as OK while it stays below the warning threshold, and escalates to a
+/// warning and then an error as it crosses the configured thresholds.
+///
+/// Like `df`, the used percentage only counts the space available to unprivileged users.
+pub struct DiskUsageStatus {
+    path: PathBuf,
+    name: String,
+    warn_percent: f64,
+    error_percent: f64,
+}
+
+impl DiskUsageStatus {
+    /// Creates a task with the given name, monitoring the file system that contains the path.
+    ///
+    /// The thresholds are percentages of used space, like `90.0`.
+    #[inline]
+    pub fn new(
+        name: &str,
+        path: impl Into<PathBuf>,
+        warn_percent: f64,
+        error_percent: f64,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            name: name.into(),
+            warn_percent,
+            error_percent,
+        }
+    }
+
+    /// Returns the path being monitored.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Task for DiskUsageStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    // The field types of `statvfs` are only `u64` on some platforms.
+    #[allow(clippy::useless_conversion)]
+    fn run(&self, status: &mut Status) {
+        status.add("Path", self.path.display());
+
+        let stats = match statvfs(&self.path) {
+            Ok(value) => value,
+            Err(err) => {
+                status.set_summary(
+                    Level::Error,
+                    format!("Failed to read file system usage: {}", err),
+                );
+                return;
+            }
+        };
+
+        let fragment_size = u64::from(stats.fragment_size());
+        let total = u64::from(stats.blocks()) * fragment_size;
+        let used = u64::from(stats.blocks() - stats.blocks_free()) * fragment_size;
+        let available = u64::from(stats.blocks_available()) * fragment_size;
+        let percent = if used + available == 0 {
+            0.0
+        } else {
+            used as f64 * 100.0 / (used + available) as f64
+        };
+
+        status.add("Used bytes", used);
+        status.add("Total bytes", total);
+        status.add("Used percentage", format!("{:.1}", percent));
+
+        if percent >= self.error_percent {
+            status.set_summary(Level::Error, "Disk usage is critical.");
+        } else if percent >= self.warn_percent {
+            status.set_summary(Level::Warn, "Disk usage is high.");
+        } else {
+            status.set_summary(Level::Ok, "Disk usage is normal.");
+        }
+    }
+}
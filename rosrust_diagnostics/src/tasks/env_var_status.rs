@@ -0,0 +1,67 @@
+use crate::{Level, Status, Task};
+use std::env;
+
+/// Diagnostic task that checks an environment variable.
+///
+/// If an expected value is provided, the variable is required to be set to it.
+/// Otherwise the variable only has to be set. A variable that is not set results in a warning,
+/// while a variable set to the wrong value results in an error.
+pub struct EnvVarStatus {
+    var_name: String,
+    expected: Option<String>,
+    name: String,
+}
+
+impl EnvVarStatus {
+    /// Creates a task with the given name, checking the provided environment variable.
+    #[inline]
+    pub fn new(name: &str, var_name: &str, expected: Option<&str>) -> Self {
+        Self {
+            var_name: var_name.into(),
+            expected: expected.map(Into::into),
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name of the environment variable being checked.
+    #[inline]
+    pub fn var_name(&self) -> &str {
+        &self.var_name
+    }
+
+    /// Returns the value the environment variable is expected to have, if any.
+    #[inline]
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+}
+
+impl Task for EnvVarStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Variable", &self.var_name);
+        if let Some(expected) = &self.expected {
+            status.add("Expected value", expected);
+        }
+
+        let value = match env::var_os(&self.var_name) {
+            Some(value) => value.to_string_lossy().into_owned(),
+            None => {
+                status.set_summary(Level::Warn, "Variable is not set.");
+                return;
+            }
+        };
+        status.add("Value", &value);
+
+        match &self.expected {
+            Some(expected) if *expected != value => {
+                status.set_summary(Level::Error, "Variable has an unexpected value.")
+            }
+            _ => status.set_summary(Level::Ok, "Variable is set."),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{Level, Status, Task};
+use std::path::{Path, PathBuf};
+
+/// Diagnostic task that checks whether a required path exists.
+///
+/// This is useful for nodes that depend on calibration or configuration files.
+/// It reports an error if nothing exists at the path, and reports what kind of entry it is
+/// otherwise.
+pub struct FileExistsStatus {
+    path: PathBuf,
+    name: String,
+}
+
+impl FileExistsStatus {
+    /// Creates a task with the given name, monitoring the provided path.
+    #[inline]
+    pub fn new(name: &str, path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Returns the path being monitored.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Task for FileExistsStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Path", self.path.display());
+
+        if !self.path.exists() {
+            status.set_summary(Level::Error, "Path does not exist.");
+            return;
+        }
+
+        let kind = match self.path.symlink_metadata() {
+            Ok(metadata) if metadata.file_type().is_symlink() => "symlink",
+            Ok(metadata) if metadata.is_dir() => "directory",
+            Ok(metadata) if metadata.is_file() => "file",
+            _ => "other",
+        };
+        status.add("Type", kind);
+        status.set_summary(Level::Ok, "Path exists.");
+    }
+}
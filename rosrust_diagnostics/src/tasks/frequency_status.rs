@@ -151,9 +151,12 @@ impl Tracker {
 
     fn clear(&mut self) {
         self.count = 0;
+        self.reset_window();
+    }
 
+    fn reset_window(&mut self) {
         self.history.clear();
-        let history_entry = HistoryEntry::new(0);
+        let history_entry = HistoryEntry::new(self.count);
 
         self.history
             .extend((0..self.window_size).map(|_| history_entry.clone()));
@@ -201,11 +204,48 @@ impl FrequencyStatus {
     ///
     /// This is good to do right before the looped routine that is being observed, to prevent
     /// the delay between task creation and the first call to give a too large initial reading.
+    ///
+    /// This also zeroes the number of events since startup. To only discard the current window,
+    /// use `reset_window`.
     #[inline]
     pub fn clear(&self) {
         self.tracker.lock().expect(FAILED_TO_LOCK).clear();
     }
 
+    /// Discards the history of the window, starting a new one from the current moment.
+    ///
+    /// This is good to do after the observed routine was intentionally paused, for example
+    /// during a reconfiguration, so the pause doesn't make the frequency look too low until
+    /// it leaves the window.
+    ///
+    /// Unlike `clear`, the number of events since startup is kept.
+    #[inline]
+    pub fn reset_window(&self) {
+        self.tracker.lock().expect(FAILED_TO_LOCK).reset_window();
+    }
+
+    /// Returns the frequency that running the task would report right now.
+    ///
+    /// Unlike running the task, this does not advance the window, so it can be called
+    /// at any rate without affecting the diagnostics.
+    ///
+    /// Returns zero if no time has passed since the window started.
+    pub fn current_frequency(&self) -> f64 {
+        let tracker = self.tracker.lock().expect(FAILED_TO_LOCK);
+        let history_start = match tracker.history.front() {
+            Some(value) => value,
+            None => return 0.0,
+        };
+
+        let events = tracker.count - history_start.count;
+        let window = (rosrust::now() - history_start.time).seconds();
+        if window > 0.0 {
+            events as f64 / window
+        } else {
+            0.0
+        }
+    }
+
     fn frequency_to_summary(&self, frequency: f64) -> (Level, &str) {
         match frequency {
             v if v == 0.0 && !self.allow_no_events => (Level::Error, "No events recorded."),
@@ -0,0 +1,101 @@
+use crate::{Level, Status, Task};
+use std::sync::Mutex;
+
+/// Operational mode reported by a `GracefulDegradationStatus`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationalMode {
+    /// Everything is working as intended.
+    Normal,
+    /// The node keeps running with reduced functionality, for the given reason.
+    Degraded(String),
+    /// The node stopped operating, for the given reason.
+    EmergencyStop(String),
+}
+
+/// Diagnostic task that reports the operational mode of a node.
+///
+/// The mode is set by the node itself. Normal operation is reported as OK,
+/// degraded operation as a warning, and an emergency stop as an error.
+pub struct GracefulDegradationStatus {
+    name: String,
+    mode: Mutex<OperationalMode>,
+}
+
+impl GracefulDegradationStatus {
+    /// Creates a task with the given name, starting in normal operation.
+    #[inline]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            mode: Mutex::new(OperationalMode::Normal),
+        }
+    }
+
+    /// Returns the current operational mode.
+    #[inline]
+    pub fn mode(&self) -> OperationalMode {
+        self.mode.lock().expect(FAILED_TO_LOCK).clone()
+    }
+
+    /// Signals that the node is operating normally.
+    #[inline]
+    pub fn set_normal(&self) {
+        self.set_mode(OperationalMode::Normal);
+    }
+
+    /// Signals that the node is operating with reduced functionality.
+    #[inline]
+    pub fn set_degraded(&self, reason: &str) {
+        self.set_mode(OperationalMode::Degraded(reason.into()));
+    }
+
+    /// Signals that the node has stopped operating.
+    #[inline]
+    pub fn set_emergency_stop(&self, reason: &str) {
+        self.set_mode(OperationalMode::EmergencyStop(reason.into()));
+    }
+
+    #[inline]
+    fn set_mode(&self, mode: OperationalMode) {
+        *self.mode.lock().expect(FAILED_TO_LOCK) = mode;
+    }
+}
+
+impl Task for GracefulDegradationStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let mode = match self.mode.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside operational mode check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        match &*mode {
+            OperationalMode::Normal => {
+                status.add("Mode", "Normal");
+                status.set_summary(Level::Ok, "Operating normally.");
+            }
+            OperationalMode::Degraded(reason) => {
+                status.add("Mode", "Degraded");
+                status.add("Reason", reason);
+                status.set_summary(Level::Warn, format!("Operating degraded: {}", reason));
+            }
+            OperationalMode::EmergencyStop(reason) => {
+                status.add("Mode", "Emergency stop");
+                status.add("Reason", reason);
+                status.set_summary(Level::Error, format!("Emergency stop: {}", reason));
+            }
+        }
+    }
+}
+
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
@@ -1,9 +1,43 @@
 //! A set of commonly useful tasks.
 
+pub use self::bandwidth_status::{BandwidthStatus, BandwidthStatusBuilder};
+pub use self::child_process_status::ChildProcessStatus;
+pub use self::disk_usage_status::DiskUsageStatus;
+pub use self::env_var_status::EnvVarStatus;
+pub use self::file_exists_status::FileExistsStatus;
 pub use self::frequency_status::{FrequencyStatus, FrequencyStatusBuilder};
+pub use self::graceful_degradation_status::{GracefulDegradationStatus, OperationalMode};
 pub use self::heartbeat::Heartbeat;
+pub use self::network_interface_status::NetworkInterfaceStatus;
+pub use self::publisher_status::PublisherStatus;
+pub use self::resource_limit_status::{ResourceLimitStatus, ResourceLimits};
+pub use self::service_call_status::ServiceCallStatus;
+pub use self::system_time_status::SystemTimeStatus;
+pub use self::tcp_connection_status::TcpConnectionStatus;
 pub use self::timestamp_status::{TimestampStatus, TimestampStatusBuilder};
+pub use self::topic_bandwidth_status::TopicBandwidthStatus;
+pub use self::topic_diagnostic::{TopicDiagnostic, TopicDiagnosticBuilder};
+pub use self::topic_echo_status::TopicEchoStatus;
+pub use self::version_status::VersionStatus;
+pub use self::watchdog::{Watchdog, WatchdogBuilder};
 
+mod bandwidth_status;
+mod child_process_status;
+mod disk_usage_status;
+mod env_var_status;
+mod file_exists_status;
 mod frequency_status;
+mod graceful_degradation_status;
 mod heartbeat;
+mod network_interface_status;
+mod publisher_status;
+mod resource_limit_status;
+mod service_call_status;
+mod system_time_status;
+mod tcp_connection_status;
 mod timestamp_status;
+mod topic_bandwidth_status;
+mod topic_diagnostic;
+mod topic_echo_status;
+mod version_status;
+mod watchdog;
@@ -0,0 +1,100 @@
+use crate::{Level, Status, Task};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Diagnostic task that monitors the link state and traffic of a network interface.
+///
+/// The information is read from `/sys/class/net`, so this task only works on Linux.
+///
+/// The link is reported as OK while it's up, and a warning is raised when the carrier is lost.
+/// Interfaces that can't be found are reported as errors.
+///
+/// Each run also reports how many bytes were received since the previous run.
+pub struct NetworkInterfaceStatus {
+    interface: String,
+    name: String,
+    last_rx_bytes: Mutex<Option<u64>>,
+}
+
+impl NetworkInterfaceStatus {
+    /// Creates a task with the given name, monitoring the provided interface, like `eth0`.
+    #[inline]
+    pub fn new(name: &str, interface: &str) -> Self {
+        Self {
+            interface: interface.into(),
+            name: name.into(),
+            last_rx_bytes: Mutex::new(None),
+        }
+    }
+
+    /// Returns the name of the interface being monitored.
+    #[inline]
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    fn read_attribute(&self, attribute: &str) -> Option<String> {
+        let path = PathBuf::from("/sys/class/net")
+            .join(&self.interface)
+            .join(attribute);
+        fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_owned())
+    }
+}
+
+impl Task for NetworkInterfaceStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Interface", &self.interface);
+
+        let operstate = match self.read_attribute("operstate") {
+            Some(value) => value,
+            None => {
+                status.set_summary(Level::Error, "Interface not found.");
+                return;
+            }
+        };
+        status.add("Operational state", &operstate);
+
+        match operstate.as_str() {
+            "up" => status.set_summary(Level::Ok, "Link is up."),
+            // Interfaces without a carrier, like the loopback, don't report their state.
+            "unknown" => status.set_summary(Level::Ok, "Link state is unknown."),
+            _ => status.set_summary(Level::Warn, "Carrier lost."),
+        }
+
+        let rx_bytes = match self
+            .read_attribute("statistics/rx_bytes")
+            .and_then(|value| value.parse::<u64>().ok())
+        {
+            Some(value) => value,
+            None => {
+                status.merge_summary(Level::Error, "Failed to read received bytes.");
+                return;
+            }
+        };
+        status.add("Total received bytes", rx_bytes);
+
+        let mut last_rx_bytes = match self.last_rx_bytes.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.merge_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside network interface check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+        if let Some(last) = *last_rx_bytes {
+            // Counters are reset when the interface is recreated.
+            status.add("Received bytes", rx_bytes.saturating_sub(last));
+        }
+        *last_rx_bytes = Some(rx_bytes);
+    }
+}
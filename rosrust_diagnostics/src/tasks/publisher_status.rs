@@ -0,0 +1,59 @@
+use crate::{Level, Status, Task};
+use rosrust::{Message, Publisher};
+use std::sync::Arc;
+
+/// Diagnostic task that checks whether enough subscribers are connected to a publisher.
+///
+/// Having no subscribers is reported as an error if at least one is required, and as a warning
+/// otherwise. Having some subscribers, but fewer than required, is also reported as a warning.
+pub struct PublisherStatus<M: Message> {
+    publisher: Arc<Publisher<M>>,
+    name: String,
+    min_subscribers: usize,
+}
+
+impl<M: Message> PublisherStatus<M> {
+    /// Creates a task with the given name, requiring at least `min_subscribers` on `publisher`.
+    pub fn new(name: &str, publisher: Arc<Publisher<M>>, min_subscribers: usize) -> Self {
+        Self {
+            publisher,
+            name: name.into(),
+            min_subscribers,
+        }
+    }
+
+    /// Returns the publisher being monitored.
+    #[inline]
+    pub fn publisher(&self) -> &Arc<Publisher<M>> {
+        &self.publisher
+    }
+
+    /// Returns the number of subscribers required.
+    #[inline]
+    pub fn min_subscribers(&self) -> usize {
+        self.min_subscribers
+    }
+}
+
+impl<M: Message> Task for PublisherStatus<M> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let subscribers = self.publisher.subscriber_count();
+        status.add("Subscribers", subscribers);
+        status.add("Minimum subscribers", self.min_subscribers);
+
+        if subscribers == 0 && self.min_subscribers > 0 {
+            status.set_summary(Level::Error, "No subscribers connected.");
+        } else if subscribers == 0 {
+            status.set_summary(Level::Warn, "No subscribers connected.");
+        } else if subscribers < self.min_subscribers {
+            status.set_summary(Level::Warn, "Fewer subscribers connected than required.");
+        } else {
+            status.set_summary(Level::Ok, "Enough subscribers connected.");
+        }
+    }
+}
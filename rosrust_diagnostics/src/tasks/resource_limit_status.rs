@@ -0,0 +1,182 @@
+use crate::{Level, Status, Task};
+use nix::unistd::{sysconf, SysconfVar};
+use std::fs;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Warning and error thresholds for the resources checked by `ResourceLimitStatus`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResourceLimits {
+    /// Resident memory, in bytes, above which a warning is reported.
+    pub memory_warn_bytes: u64,
+    /// Resident memory, in bytes, above which an error is reported.
+    pub memory_error_bytes: u64,
+    /// CPU usage, as a percentage of a single core, above which a warning is reported.
+    pub cpu_warn_percent: f64,
+    /// CPU usage, as a percentage of a single core, above which an error is reported.
+    pub cpu_error_percent: f64,
+    /// Number of threads above which a warning is reported.
+    pub threads_warn: u64,
+    /// Number of threads above which an error is reported.
+    pub threads_error: u64,
+}
+
+/// Diagnostic task that monitors the memory, CPU and thread usage of the node's process.
+///
+/// All three resources are checked in a single run, and the status has the level of the worst
+/// one. CPU usage is averaged over the time since the previous run, or since the task was
+/// created for the first run. It can exceed 100% when more than one core is used.
+///
+/// The usage is read from `/proc`, so this task only works on Linux.
+pub struct ResourceLimitStatus {
+    name: String,
+    limits: ResourceLimits,
+    last_cpu_sample: Mutex<Option<CpuSample>>,
+}
+
+#[derive(Clone, Copy)]
+struct CpuSample {
+    time: Instant,
+    cpu_seconds: f64,
+}
+
+impl ResourceLimitStatus {
+    /// Creates a task with the given name and resource limits.
+    pub fn new(name: &str, limits: ResourceLimits) -> Self {
+        Self {
+            name: name.into(),
+            limits,
+            last_cpu_sample: Mutex::new(read_cpu_sample().ok()),
+        }
+    }
+
+    /// Returns the limits being checked.
+    #[inline]
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    fn check_memory_and_threads(&self, status: &mut Status) {
+        let (memory_bytes, threads) = match read_memory_and_threads() {
+            Ok(value) => value,
+            Err(err) => {
+                status.merge_summary(Level::Error, &err);
+                return;
+            }
+        };
+
+        status.add("Memory (bytes)", memory_bytes);
+        status.add("Threads", threads);
+        if memory_bytes > self.limits.memory_error_bytes {
+            status.merge_summary(Level::Error, "Memory usage is critical.");
+        } else if memory_bytes > self.limits.memory_warn_bytes {
+            status.merge_summary(Level::Warn, "Memory usage is high.");
+        }
+        if threads > self.limits.threads_error {
+            status.merge_summary(Level::Error, "Thread count is critical.");
+        } else if threads > self.limits.threads_warn {
+            status.merge_summary(Level::Warn, "Thread count is high.");
+        }
+    }
+
+    fn check_cpu(&self, status: &mut Status) {
+        let sample = match read_cpu_sample() {
+            Ok(value) => value,
+            Err(err) => {
+                status.merge_summary(Level::Error, &err);
+                return;
+            }
+        };
+
+        let mut last_cpu_sample = match self.last_cpu_sample.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.merge_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside resource limit check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+        let previous = last_cpu_sample.replace(sample);
+        let previous = match previous {
+            Some(value) => value,
+            // Nothing to compare against yet, the next run will have a result.
+            None => return,
+        };
+
+        let elapsed = sample.time.duration_since(previous.time).as_secs_f64();
+        let percent = if elapsed > 0.0 {
+            (sample.cpu_seconds - previous.cpu_seconds) * 100.0 / elapsed
+        } else {
+            0.0
+        };
+
+        status.add("CPU usage (%)", format!("{:.1}", percent));
+        if percent > self.limits.cpu_error_percent {
+            status.merge_summary(Level::Error, "CPU usage is critical.");
+        } else if percent > self.limits.cpu_warn_percent {
+            status.merge_summary(Level::Warn, "CPU usage is high.");
+        }
+    }
+}
+
+impl Task for ResourceLimitStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.set_summary(Level::Ok, "Resource usage is normal.");
+        self.check_memory_and_threads(status);
+        self.check_cpu(status);
+    }
+}
+
+fn read_memory_and_threads() -> Result<(u64, u64), String> {
+    let contents = fs::read_to_string("/proc/self/status")
+        .map_err(|err| format!("Failed to read process status: {}", err))?;
+
+    let field = |name: &str| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|value| value.split_whitespace().next())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| format!("Process status is missing {}", name))
+    };
+    // The resident set size is reported in kB.
+    let memory_bytes = field("VmRSS:")? * 1024;
+    let threads = field("Threads:")?;
+    Ok((memory_bytes, threads))
+}
+
+fn read_cpu_sample() -> Result<CpuSample, String> {
+    let time = Instant::now();
+    let contents = fs::read_to_string("/proc/self/stat")
+        .map_err(|err| format!("Failed to read process CPU time: {}", err))?;
+    let ticks_per_second = match sysconf(SysconfVar::CLK_TCK) {
+        Ok(Some(value)) if value > 0 => value as f64,
+        _ => return Err("Failed to read clock ticks per second".into()),
+    };
+
+    // The process name may contain spaces, so fields are counted from the end of it. The user
+    // and system CPU times are the 14th and 15th fields, while the state is the 3rd.
+    let fields = contents
+        .rsplit_once(')')
+        .map(|(_, rest)| rest.split_whitespace().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let ticks = |index: usize| {
+        fields
+            .get(index)
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+    match (ticks(11), ticks(12)) {
+        (Some(user), Some(system)) => Ok(CpuSample {
+            time,
+            cpu_seconds: (user + system) as f64 / ticks_per_second,
+        }),
+        _ => Err("Process CPU time is malformed".into()),
+    }
+}
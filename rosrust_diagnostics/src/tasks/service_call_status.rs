@@ -0,0 +1,98 @@
+use crate::{Level, Status, Task};
+use rosrust::api::error::tcpros::ErrorKind;
+use rosrust::{Client, RosMsg, ServicePair};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Diagnostic task that checks whether a service answers requests.
+///
+/// Every time the task runs, the service is called with the test request, and the call is reported
+/// as an error if the service can't be reached, fails, or doesn't respond within the timeout.
+pub struct ServiceCallStatus<S: ServicePair> {
+    name: String,
+    topic: String,
+    // Requests don't have to be `Clone` or `Sync`, so a fresh one is decoded for every call.
+    test_request: Vec<u8>,
+    timeout: Duration,
+    // Looked up on the first call, and again after a call fails, in case the service moved.
+    client: Mutex<Option<Client<S>>>,
+}
+
+impl<S: ServicePair> ServiceCallStatus<S> {
+    /// Creates a task with the given name, calling the service on `topic` with `test_request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the test request can't be encoded.
+    pub fn new(
+        name: &str,
+        topic: &str,
+        test_request: S::Request,
+        timeout: Duration,
+    ) -> rosrust::error::Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            topic: topic.into(),
+            test_request: test_request.encode_vec()?,
+            timeout,
+            client: Mutex::new(None),
+        })
+    }
+
+    /// Returns the service being monitored.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    fn call(&self) -> Result<(), String> {
+        let mut client = self.client.lock().map_err(|_| {
+            "Failed to acquire Mutex lock inside service call check. This can only be caused by a thread unexpectedly crashing inside the node."
+        })?;
+        let request = S::Request::decode_slice(&self.test_request)
+            .map_err(|err| format!("Failed to create request: {}", err))?;
+        let cli = match client.take() {
+            Some(cli) => cli,
+            None => rosrust::client::<S>(&self.topic)
+                .map_err(|err| format!("Service unavailable: {}", err))?,
+        };
+
+        // The timeout covers the whole call, so nothing is left running once it returns.
+        let response = cli.req_with_timeout(&request, self.timeout);
+        if response.is_ok() {
+            *client = Some(cli);
+        }
+        match response {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(message)) => Err(format!("Service responded with an error: {}", message)),
+            Err(err) if matches!(err.kind(), ErrorKind::ServiceResponseTimeout) => {
+                Err("Service call timed out.".into())
+            }
+            Err(err) => Err(format!("Service call failed: {}", err)),
+        }
+    }
+}
+
+impl<S: ServicePair> Task for ServiceCallStatus<S> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Service", &self.topic);
+        status.add("Timeout (s)", self.timeout.as_secs_f64());
+
+        let start = Instant::now();
+        match self.call() {
+            Ok(()) => {
+                status.add(
+                    "Call duration (ms)",
+                    format!("{:.3}", start.elapsed().as_secs_f64() * 1000.0),
+                );
+                status.set_summary(Level::Ok, "Service call succeeded.");
+            }
+            Err(err) => status.set_summary(Level::Error, err),
+        }
+    }
+}
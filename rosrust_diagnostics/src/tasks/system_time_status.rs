@@ -0,0 +1,59 @@
+use crate::{Level, Status, Task};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Diagnostic task that checks whether ROS time is in sync with the wall clock.
+///
+/// This is useful in setups mixing simulated and real time, where `rosrust::now()` is driven by
+/// the `/clock` topic. A warning is reported if the two clocks differ by more than
+/// the maximum offset.
+pub struct SystemTimeStatus {
+    name: String,
+    max_offset_ms: f64,
+}
+
+impl SystemTimeStatus {
+    /// Creates a task with the given name, allowing the clocks to differ by `max_offset_ms`.
+    #[inline]
+    pub fn new(name: &str, max_offset_ms: f64) -> Self {
+        Self {
+            name: name.into(),
+            max_offset_ms,
+        }
+    }
+
+    /// Returns the maximum allowed offset between the clocks, in milliseconds.
+    #[inline]
+    pub fn max_offset_ms(&self) -> f64 {
+        self.max_offset_ms
+    }
+}
+
+impl Task for SystemTimeStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let ros_time = rosrust::now().seconds();
+        let system_time = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(value) => value.as_secs_f64(),
+            Err(_err) => {
+                status.set_summary(Level::Error, "System time is before the Unix epoch.");
+                return;
+            }
+        };
+        let offset_ms = (ros_time - system_time) * 1000.0;
+
+        status.add("ROS time (s)", ros_time);
+        status.add("System time (s)", system_time);
+        status.add("Offset (ms)", offset_ms);
+        status.add("Maximum acceptable offset (ms)", self.max_offset_ms);
+
+        if offset_ms.abs() > self.max_offset_ms {
+            status.set_summary(Level::Warn, "ROS time is out of sync with system time.");
+        } else {
+            status.set_summary(Level::Ok, "ROS time is in sync with system time.");
+        }
+    }
+}
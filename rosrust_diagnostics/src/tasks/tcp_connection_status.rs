@@ -0,0 +1,103 @@
+use crate::{Level, Status, Task};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Diagnostic task that checks whether a TCP endpoint accepts connections.
+///
+/// A connection is attempted when the task runs, and reported as an error if it doesn't succeed
+/// within the timeout.
+///
+/// To avoid hammering the endpoint when diagnostics are updated often, the outcome of a check is
+/// reused for as long as it's younger than the staleness period.
+pub struct TcpConnectionStatus {
+    address: SocketAddr,
+    name: String,
+    timeout: Duration,
+    staleness: Duration,
+    last_check: Mutex<Option<Check>>,
+}
+
+struct Check {
+    time: Instant,
+    result: Result<Duration, String>,
+}
+
+impl TcpConnectionStatus {
+    /// Creates a task with the given name, connecting to the provided address.
+    ///
+    /// Results are reused for `staleness` before the endpoint is checked again.
+    /// A zero staleness period checks the endpoint on every run.
+    #[inline]
+    pub fn new(name: &str, address: SocketAddr, timeout: Duration, staleness: Duration) -> Self {
+        Self {
+            address,
+            name: name.into(),
+            timeout,
+            staleness,
+            last_check: Mutex::new(None),
+        }
+    }
+
+    /// Returns the address being monitored.
+    #[inline]
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    fn connect(&self) -> Check {
+        let time = Instant::now();
+        let result = TcpStream::connect_timeout(&self.address, self.timeout)
+            .map(|_| time.elapsed())
+            .map_err(|err| err.to_string());
+        Check { time, result }
+    }
+}
+
+impl Task for TcpConnectionStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Address", self.address);
+
+        let mut last_check = match self.last_check.lock() {
+            Ok(value) => value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside TCP connection check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        let is_stale = match &*last_check {
+            Some(check) => check.time.elapsed() >= self.staleness,
+            None => true,
+        };
+        if is_stale {
+            *last_check = Some(self.connect());
+        }
+        let check = last_check.as_ref().unwrap();
+
+        status.add(
+            "Last check age",
+            format!("{:.3}s", check.time.elapsed().as_secs_f64()),
+        );
+        match &check.result {
+            Ok(latency) => {
+                status.add(
+                    "Connection latency",
+                    format!("{:.3}ms", latency.as_secs_f64() * 1000.0),
+                );
+                status.set_summary(Level::Ok, "Connection succeeded.");
+            }
+            Err(err) => {
+                status.set_summary(Level::Error, format!("Connection failed: {}", err));
+            }
+        }
+    }
+}
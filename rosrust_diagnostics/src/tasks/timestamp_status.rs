@@ -1,7 +1,7 @@
 #![allow(clippy::float_cmp)]
 
 use crate::{Level, Status, Task};
-use rosrust::Time;
+use rosrust::{Duration, Time};
 use std::sync::Mutex;
 
 /// The structure for building a timestamp status task.
@@ -41,6 +41,22 @@ impl<'a> TimestampStatusBuilder<'a> {
         self
     }
 
+    /// Sets the minimum difference in timestamp that is expected, as a duration.
+    ///
+    /// Same as `min_acceptable`. Negative durations allow timestamps from the future.
+    #[inline]
+    pub fn min_acceptable_duration(&mut self, value: Duration) -> &mut Self {
+        self.min_acceptable(value.seconds())
+    }
+
+    /// Sets the maximum difference in timestamp that is expected, as a duration.
+    ///
+    /// Same as `max_acceptable`.
+    #[inline]
+    pub fn max_acceptable_duration(&mut self, value: Duration) -> &mut Self {
+        self.max_acceptable(value.seconds())
+    }
+
     /// Sets the name of the task.
     ///
     /// Defaults to "Timestamp Status".
@@ -273,6 +289,17 @@ mod tests {
         assert_eq!(tracker.delta_range.max, 0.0);
     }
 
+    #[test]
+    fn timestamp_status_builder_accepts_durations() {
+        let ts = TimestampStatus::builder()
+            .min_acceptable_duration(Duration::from_nanos(-500_000_000))
+            .max_acceptable_duration(Duration::from_seconds(2))
+            .build();
+
+        assert_eq!(ts.acceptable.min, -0.5);
+        assert_eq!(ts.acceptable.max, 2.0);
+    }
+
     #[test]
     fn counter_defaults_to_zeros() {
         let counter = Counter::default();
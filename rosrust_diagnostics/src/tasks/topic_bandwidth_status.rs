@@ -0,0 +1,148 @@
+use crate::{Level, Status, Task};
+use rosrust::{Message, Subscriber, Time};
+use std::io;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Diagnostic task that subscribes to a topic and monitors its bandwidth.
+///
+/// Every run reports the bandwidth, message rate and average message size since the previous
+/// run, or since the task was created for the first run. Bandwidth above the warning or error
+/// threshold is reported with that level.
+///
+/// The size of a message is the length of its serialized form, without any connection overhead.
+///
+/// To monitor bandwidth against expected bounds over a sliding window instead, feed message
+/// sizes into a `BandwidthStatus`.
+pub struct TopicBandwidthStatus<M: Message> {
+    topic: String,
+    name: String,
+    warn_bytes_per_sec: f64,
+    error_bytes_per_sec: f64,
+    tracker: Arc<Mutex<Tracker>>,
+    _subscriber: Subscriber,
+    _phantom: PhantomData<fn(M)>,
+}
+
+struct Tracker {
+    messages: usize,
+    bytes: usize,
+    since: Time,
+}
+
+impl Tracker {
+    fn new() -> Self {
+        Self {
+            messages: 0,
+            bytes: 0,
+            since: rosrust::now(),
+        }
+    }
+}
+
+impl<M: Message> TopicBandwidthStatus<M> {
+    /// Creates a task with the given name and bandwidth thresholds, subscribing to the provided topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing to the topic fails.
+    pub fn new(
+        name: &str,
+        topic: &str,
+        warn_bytes_per_sec: f64,
+        error_bytes_per_sec: f64,
+    ) -> rosrust::error::Result<Self> {
+        let tracker = Arc::new(Mutex::new(Tracker::new()));
+
+        let callback_tracker = Arc::clone(&tracker);
+        let subscriber = rosrust::subscribe(topic, 100, move |message: M| {
+            let mut counter = ByteCounter(0);
+            if message.encode(&mut counter).is_err() {
+                return;
+            }
+            if let Ok(mut tracker) = callback_tracker.lock() {
+                tracker.messages += 1;
+                tracker.bytes += counter.0;
+            }
+        })?;
+
+        Ok(Self {
+            topic: topic.into(),
+            name: name.into(),
+            warn_bytes_per_sec,
+            error_bytes_per_sec,
+            tracker,
+            _subscriber: subscriber,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the topic being monitored.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+}
+
+impl<M: Message> Task for TopicBandwidthStatus<M> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Topic", &self.topic);
+
+        let tracker = match self.tracker.lock() {
+            Ok(mut value) => std::mem::replace(&mut *value, Tracker::new()),
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside bandwidth check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        let window = (rosrust::now() - tracker.since).seconds();
+        let (bandwidth, rate) = if window > 0.0 {
+            (
+                tracker.bytes as f64 / window,
+                tracker.messages as f64 / window,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        status.add("Bandwidth (B/s)", bandwidth);
+        status.add("Message rate (Hz)", rate);
+        if tracker.messages > 0 {
+            status.add(
+                "Average message size (B)",
+                tracker.bytes as f64 / tracker.messages as f64,
+            );
+        }
+
+        if bandwidth > self.error_bytes_per_sec {
+            status.set_summary(Level::Error, "Bandwidth too high.");
+        } else if bandwidth > self.warn_bytes_per_sec {
+            status.set_summary(Level::Warn, "Bandwidth high.");
+        } else {
+            status.set_summary(Level::Ok, "Bandwidth is normal.");
+        }
+    }
+}
+
+// Counts the bytes of a serialized message, without keeping them around.
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
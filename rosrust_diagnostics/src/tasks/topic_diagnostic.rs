@@ -0,0 +1,194 @@
+use super::{FrequencyStatusBuilder, TimestampStatusBuilder};
+use crate::{FrequencyStatus, Status, Task, TimestampStatus};
+use rosrust::{Duration, Message, Subscriber};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// The structure for building a topic diagnostic task.
+///
+/// Use `TopicDiagnostic::builder()` to create an instance of this structure.
+///
+/// The frequency and timestamp parameters have the same defaults as in
+/// `FrequencyStatusBuilder` and `TimestampStatusBuilder`.
+pub struct TopicDiagnosticBuilder<'a, M: Message> {
+    frequency: FrequencyStatusBuilder<'a>,
+    timestamp: TimestampStatusBuilder<'a>,
+    topic: &'a str,
+    queue_size: usize,
+    name: &'a str,
+    _phantom: PhantomData<fn(M)>,
+}
+
+impl<'a, M: Message> TopicDiagnosticBuilder<'a, M> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            frequency: FrequencyStatus::builder(),
+            timestamp: TimestampStatus::builder(),
+            topic: "",
+            queue_size: 100,
+            name: "Topic Diagnostic",
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the topic to subscribe to.
+    ///
+    /// This has to be set before building the task.
+    #[inline]
+    pub fn topic(&mut self, topic: &'a str) -> &mut Self {
+        self.topic = topic;
+        self
+    }
+
+    /// Sets the queue size of the subscriber.
+    ///
+    /// Defaults to `100`.
+    #[inline]
+    pub fn queue_size(&mut self, value: usize) -> &mut Self {
+        self.queue_size = value;
+        self
+    }
+
+    /// Sets the name of the task.
+    ///
+    /// Defaults to "Topic Diagnostic".
+    #[inline]
+    pub fn name(&mut self, name: &'a str) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the minimum frequency that is expected.
+    #[inline]
+    pub fn min_frequency(&mut self, value: f64) -> &mut Self {
+        self.frequency.min_frequency(value);
+        self
+    }
+
+    /// Sets the maximum frequency that is expected.
+    #[inline]
+    pub fn max_frequency(&mut self, value: f64) -> &mut Self {
+        self.frequency.max_frequency(value);
+        self
+    }
+
+    /// Sets the tolerance to how far out of bounds a frequency is allowed to go.
+    #[inline]
+    pub fn tolerance(&mut self, value: f64) -> &mut Self {
+        self.frequency.tolerance(value);
+        self
+    }
+
+    /// Sets the number of ticks we're averaging while estimating the frequency.
+    #[inline]
+    pub fn window_size(&mut self, value: usize) -> &mut Self {
+        self.frequency.window_size(value);
+        self
+    }
+
+    /// Sets whether or not a lack of messages within the window is an error.
+    #[inline]
+    pub fn allow_no_events(&mut self, allow_no_events: bool) -> &mut Self {
+        self.frequency.allow_no_events(allow_no_events);
+        self
+    }
+
+    /// Sets the minimum difference in timestamp that is expected, in seconds.
+    #[inline]
+    pub fn min_acceptable(&mut self, value: f64) -> &mut Self {
+        self.timestamp.min_acceptable(value);
+        self
+    }
+
+    /// Sets the maximum difference in timestamp that is expected, in seconds.
+    #[inline]
+    pub fn max_acceptable(&mut self, value: f64) -> &mut Self {
+        self.timestamp.max_acceptable(value);
+        self
+    }
+
+    /// Sets the minimum difference in timestamp that is expected, as a duration.
+    #[inline]
+    pub fn min_acceptable_duration(&mut self, value: Duration) -> &mut Self {
+        self.timestamp.min_acceptable_duration(value);
+        self
+    }
+
+    /// Sets the maximum difference in timestamp that is expected, as a duration.
+    #[inline]
+    pub fn max_acceptable_duration(&mut self, value: Duration) -> &mut Self {
+        self.timestamp.max_acceptable_duration(value);
+        self
+    }
+
+    /// Builds the topic diagnostic with the provided parameters, subscribing to the topic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing to the topic fails.
+    pub fn build(&self) -> rosrust::error::Result<TopicDiagnostic<M>> {
+        let frequency = Arc::new(self.frequency.build());
+        let timestamp = Arc::new(self.timestamp.build());
+
+        let callback_frequency = Arc::clone(&frequency);
+        let callback_timestamp = Arc::clone(&timestamp);
+        let subscriber = rosrust::subscribe(self.topic, self.queue_size, move |message: M| {
+            callback_frequency.tick();
+            if let Some(stamp) = message.header_stamp() {
+                callback_timestamp.tick(stamp);
+            }
+        })?;
+
+        Ok(TopicDiagnostic {
+            topic: self.topic.into(),
+            name: self.name.into(),
+            frequency,
+            timestamp,
+            _subscriber: subscriber,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// Diagnostic task that monitors both the rate and the timestamps of messages on a topic.
+///
+/// Every message received ticks a `FrequencyStatus`, and a `TimestampStatus` with the stamp
+/// from the message's header. Messages without a header only tick the frequency, so the
+/// timestamp status keeps warning about a lack of data.
+///
+/// The reported status has the worst level of the two.
+pub struct TopicDiagnostic<M: Message> {
+    topic: String,
+    name: String,
+    frequency: Arc<FrequencyStatus>,
+    timestamp: Arc<TimestampStatus>,
+    _subscriber: Subscriber,
+    _phantom: PhantomData<fn(M)>,
+}
+
+impl<M: Message> TopicDiagnostic<M> {
+    /// Creates a builder for a new topic diagnostic task.
+    #[inline]
+    pub fn builder<'a>() -> TopicDiagnosticBuilder<'a, M> {
+        TopicDiagnosticBuilder::new()
+    }
+
+    /// Returns the topic being monitored.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+}
+
+impl<M: Message> Task for TopicDiagnostic<M> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Topic", &self.topic);
+        run_diagnostics!(status, *self.frequency, *self.timestamp);
+    }
+}
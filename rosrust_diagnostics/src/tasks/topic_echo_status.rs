@@ -0,0 +1,123 @@
+use crate::{FrequencyStatus, Level, Status, Task};
+use rosrust::{Duration, Message, Subscriber, Time};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Diagnostic task that subscribes to a topic and monitors the messages arriving on it.
+///
+/// The rate of incoming messages is checked by the provided `FrequencyStatus`, and
+/// an error is reported if no message has arrived for longer than the allowed staleness.
+/// Only the arrival of messages is monitored, their content is ignored.
+pub struct TopicEchoStatus<M: Message> {
+    topic: String,
+    name: String,
+    max_staleness: Duration,
+    frequency: Arc<FrequencyStatus>,
+    last_received: Arc<Mutex<Option<Time>>>,
+    _subscriber: Subscriber,
+    _phantom: PhantomData<fn(M)>,
+}
+
+impl<M: Message> TopicEchoStatus<M> {
+    /// Creates a task with the given name, subscribing to the provided topic.
+    ///
+    /// The subscriber has a queue size of `100`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing to the topic fails.
+    #[inline]
+    pub fn new(
+        name: &str,
+        topic: &str,
+        frequency: FrequencyStatus,
+        max_staleness: Duration,
+    ) -> rosrust::error::Result<Self> {
+        Self::with_queue_size(name, topic, frequency, max_staleness, 100)
+    }
+
+    /// Creates a task with the given name, subscribing to the provided topic with the given
+    /// queue size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing to the topic fails.
+    pub fn with_queue_size(
+        name: &str,
+        topic: &str,
+        frequency: FrequencyStatus,
+        max_staleness: Duration,
+        queue_size: usize,
+    ) -> rosrust::error::Result<Self> {
+        let frequency = Arc::new(frequency);
+        let last_received = Arc::new(Mutex::new(None));
+
+        let callback_frequency = Arc::clone(&frequency);
+        let callback_last_received = Arc::clone(&last_received);
+        let subscriber = rosrust::subscribe(topic, queue_size, move |_: M| {
+            callback_frequency.tick();
+            if let Ok(mut last_received) = callback_last_received.lock() {
+                *last_received = Some(rosrust::now());
+            }
+        })?;
+
+        Ok(Self {
+            topic: topic.into(),
+            name: name.into(),
+            max_staleness,
+            frequency,
+            last_received,
+            _subscriber: subscriber,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the topic being monitored.
+    #[inline]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Returns the time at which the last message was received, if any arrived yet.
+    pub fn last_received(&self) -> Option<Time> {
+        *self.last_received.lock().ok()?
+    }
+}
+
+impl<M: Message> Task for TopicEchoStatus<M> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Topic", &self.topic);
+        self.frequency.run(status);
+
+        let last_received = match self.last_received.lock() {
+            Ok(value) => *value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside topic echo check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        let last_received = match last_received {
+            Some(value) => value,
+            None => {
+                status.merge_summary(Level::Error, "No messages received.");
+                return;
+            }
+        };
+
+        let age = rosrust::now() - last_received;
+        status.add("Last message time (s)", last_received.seconds());
+        status.add("Time since last message (s)", age.seconds());
+        if age > self.max_staleness {
+            status.merge_summary(Level::Error, "Messages are stale.");
+        }
+    }
+}
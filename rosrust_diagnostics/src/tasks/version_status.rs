@@ -0,0 +1,75 @@
+use crate::{Level, Status, Task};
+use semver::Version;
+
+/// Diagnostic task that reports the version of a software component.
+///
+/// If an expected version is provided, the versions are compared according to semantic
+/// versioning. A different major version results in an error, while any other difference
+/// results in a warning.
+pub struct VersionStatus {
+    component: String,
+    version: String,
+    expected_version: Option<String>,
+}
+
+impl VersionStatus {
+    /// Creates a task reporting the version of the given component.
+    ///
+    /// The name of the component is used as the name of the task.
+    #[inline]
+    pub fn new(component: &str, version: &str, expected_version: Option<&str>) -> Self {
+        Self {
+            component: component.into(),
+            version: version.into(),
+            expected_version: expected_version.map(Into::into),
+        }
+    }
+
+    /// Returns the reported version.
+    #[inline]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the version the component is expected to have, if any.
+    #[inline]
+    pub fn expected_version(&self) -> Option<&str> {
+        self.expected_version.as_deref()
+    }
+}
+
+impl Task for VersionStatus {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.component
+    }
+
+    fn run(&self, status: &mut Status) {
+        status.add("Version", &self.version);
+
+        let expected = match &self.expected_version {
+            Some(expected) => expected,
+            None => {
+                status.set_summary(Level::Ok, &self.version);
+                return;
+            }
+        };
+        status.add("Expected version", expected);
+
+        let (version, expected) = match (Version::parse(&self.version), Version::parse(expected)) {
+            (Ok(version), Ok(expected)) => (version, expected),
+            _ => {
+                status.set_summary(Level::Error, "Version is not valid semantic versioning.");
+                return;
+            }
+        };
+
+        if version.major != expected.major {
+            status.set_summary(Level::Error, "Major version mismatch.");
+        } else if version != expected {
+            status.set_summary(Level::Warn, "Version mismatch.");
+        } else {
+            status.set_summary(Level::Ok, &self.version);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{Level, Status, Task};
+use rosrust::{Duration, Time};
+use std::sync::Mutex;
+
+/// The structure for building a watchdog task.
+///
+/// Use `Watchdog::builder()` to create an instance of this structure.
+pub struct WatchdogBuilder<'a> {
+    name: &'a str,
+    timeout: Duration,
+    error_message: &'a str,
+}
+
+impl<'a> WatchdogBuilder<'a> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            name: "Watchdog",
+            timeout: Duration::from_seconds(1),
+            error_message: "No events within timeout.",
+        }
+    }
+
+    /// Sets the name of the task.
+    ///
+    /// Defaults to "Watchdog".
+    #[inline]
+    pub fn name(&mut self, name: &'a str) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets how long the watchdog waits for an event before reporting an error.
+    ///
+    /// Defaults to one second.
+    #[inline]
+    pub fn timeout(&mut self, value: Duration) -> &mut Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Sets the summary message reported when the timeout is exceeded.
+    ///
+    /// Defaults to "No events within timeout."
+    #[inline]
+    pub fn error_message(&mut self, message: &'a str) -> &mut Self {
+        self.error_message = message;
+        self
+    }
+
+    /// Builds the watchdog with the provided parameters.
+    #[inline]
+    pub fn build(&self) -> Watchdog {
+        Watchdog::new(self.name.into(), self.timeout, self.error_message.into())
+    }
+}
+
+/// Diagnostic task that monitors whether an event keeps occurring.
+///
+/// Unlike `FrequencyStatus`, which checks the rate of calls to its tick method, this task
+/// only checks for liveness. It reports an error if there has been no tick within the
+/// timeout, and OK otherwise.
+///
+/// Unlike `Heartbeat`, which always reports the node as alive, this requires the monitored
+/// routine to keep signalling.
+pub struct Watchdog {
+    name: String,
+    timeout: Duration,
+    error_message: String,
+    last_event: Mutex<Option<Time>>,
+}
+
+impl Watchdog {
+    /// Creates a builder for a new watchdog task.
+    #[inline]
+    pub fn builder<'a>() -> WatchdogBuilder<'a> {
+        WatchdogBuilder::new()
+    }
+
+    /// Creates a new watchdog based on the provided parameters.
+    ///
+    /// Look at the `WatchdogBuilder` for more information about the parameters and
+    /// reasonable defaults.
+    #[inline]
+    pub fn new(name: String, timeout: Duration, error_message: String) -> Self {
+        Self {
+            name,
+            timeout,
+            error_message,
+            last_event: Mutex::new(None),
+        }
+    }
+
+    /// Signals that an event has occurred.
+    #[inline]
+    pub fn tick(&self) {
+        *self.last_event.lock().expect(FAILED_TO_LOCK) = Some(rosrust::now());
+    }
+
+    /// Forgets the last event, so the watchdog reports an error until the next tick.
+    #[inline]
+    pub fn clear(&self) {
+        *self.last_event.lock().expect(FAILED_TO_LOCK) = None;
+    }
+}
+
+impl Task for Watchdog {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, status: &mut Status) {
+        let last_event = match self.last_event.lock() {
+            Ok(value) => *value,
+            Err(_err) => {
+                status.set_summary(
+                    Level::Error,
+                    "Failed to acquire Mutex lock inside watchdog check. This can only be caused by a thread unexpectedly crashing inside the node.",
+                );
+                return;
+            }
+        };
+
+        status.add("Timeout (s)", self.timeout.seconds());
+
+        let since_last_event = match last_event {
+            Some(time) => rosrust::now() - time,
+            None => {
+                status.set_summary(Level::Error, &self.error_message);
+                return;
+            }
+        };
+
+        status.add("Time since last event (s)", since_last_event.seconds());
+
+        if since_last_event > self.timeout {
+            status.set_summary(Level::Error, &self.error_message);
+        } else {
+            status.set_summary(Level::Ok, "Events received within timeout.");
+        }
+    }
+}
+
+static FAILED_TO_LOCK: &str = "Failed to acquire lock";
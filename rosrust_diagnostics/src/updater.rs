@@ -173,6 +173,34 @@ impl<'a> Updater<'a> {
         self.publish(self.make_update_statuses(extra_tasks))
     }
 
+    /// Runs every task added to the updater and returns the resulting statuses, without
+    /// publishing them.
+    ///
+    /// Useful for reporting the diagnostics through other means, such as an HTTP health check.
+    pub fn snapshot(&self) -> Vec<Status> {
+        self.tasks.iter().map(|task| self.run_task(*task)).collect()
+    }
+
+    fn run_task(&self, task: &dyn Task) -> Status {
+        let mut status = Status {
+            name: task.name().into(),
+            hardware_id: self.hardware_id.clone(),
+            level: Level::Error,
+            message: "No message was set".into(),
+            values: vec![],
+        };
+        task.run(&mut status);
+        if self.verbose && status.level != Level::Ok {
+            rosrust::ros_warn!(
+                "Non-zero diagnostic status. Name: '{}', status {}: '{}'",
+                status.name,
+                status.level as i8,
+                status.message,
+            );
+        }
+        status
+    }
+
     /// Outputs a message on all the known tasks.
     ///
     /// Useful if something drastic is happening such as shutdown or a self-test.
@@ -258,24 +286,9 @@ impl<'a> UpdaterLowLevelExt for Updater<'a> {
         self.map_over_tasks(extra_tasks, |task| self.make_update_status(task))
     }
 
+    #[inline]
     fn make_update_status(&self, task: &dyn Task) -> DiagnosticStatus {
-        let mut status = Status {
-            name: task.name().into(),
-            hardware_id: self.hardware_id.clone(),
-            level: Level::Error,
-            message: "No message was set".into(),
-            values: vec![],
-        };
-        task.run(&mut status);
-        if self.verbose && status.level != Level::Ok {
-            rosrust::ros_warn!(
-                "Non-zero diagnostic status. Name: '{}', status {}: '{}'",
-                status.name,
-                status.level as i8,
-                status.message,
-            );
-        }
-        status.into()
+        self.run_task(task).into()
     }
 
     #[inline]
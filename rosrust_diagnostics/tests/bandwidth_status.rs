@@ -0,0 +1,60 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{BandwidthStatus, Level, Status, Task};
+
+mod util;
+
+#[test]
+fn bandwidth_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::BandwidthStatusTest);
+    rosrust::init("bandwidth_status_test");
+
+    let bs = BandwidthStatus::builder()
+        .window_size(2)
+        .min_bytes_per_sec(1000.0)
+        .max_bytes_per_sec(2000.0)
+        .tolerance(0.5)
+        .build();
+
+    bs.tick(100);
+    rosrust::sleep(Duration::from_nanos(20_000_000));
+    let mut status0 = Status::default();
+    bs.run(&mut status0);
+    rosrust::sleep(Duration::from_nanos(100_000_000));
+    bs.tick(150);
+    let mut status1 = Status::default();
+    bs.run(&mut status1);
+    rosrust::sleep(Duration::from_nanos(300_000_000));
+    let mut status2 = Status::default();
+    bs.run(&mut status2);
+    bs.clear();
+    let mut status3 = Status::default();
+    bs.run(&mut status3);
+
+    assert_eq!(
+        status0.level,
+        Level::Warn,
+        "Max bandwidth exceeded but not reported"
+    );
+    assert_eq!(
+        status1.level,
+        Level::Ok,
+        "Within bandwidth bounds but reported error"
+    );
+    assert_eq!(
+        status2.level,
+        Level::Warn,
+        "Min bandwidth exceeded but not reported"
+    );
+    assert_eq!(status3.level, Level::Error, "Freshly cleared should fail");
+    assert_eq!(
+        status0.name, "",
+        "Name should not be set by BandwidthStatus"
+    );
+    assert_eq!(
+        bs.name(),
+        "Bandwidth Status",
+        "Name should be \"Bandwidth Status\""
+    );
+
+    assert_eq!(util::value_of(&status1, "Bytes in window"), Some("250"));
+}
@@ -0,0 +1,41 @@
+use rosrust_diagnostics::{ChildProcessStatus, Level, Status, Task};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+mod util;
+
+#[test]
+fn running_process_is_ok() {
+    let child = Command::new("sleep")
+        .arg("10")
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let child = Arc::new(Mutex::new(child));
+    let task = ChildProcessStatus::new("Sleeper", Arc::clone(&child));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(task.name(), "Sleeper");
+    assert_eq!(util::value_of(&status, "Exit code"), None);
+
+    let mut child = child.lock().unwrap();
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn exited_process_is_an_error() {
+    let mut child = Command::new("sh").args(&["-c", "exit 3"]).spawn().unwrap();
+    child.wait().unwrap();
+    let task = ChildProcessStatus::new("Worker", Arc::new(Mutex::new(child)));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Process exited with code 3.");
+    assert_eq!(util::value_of(&status, "Exit code"), Some("3"));
+}
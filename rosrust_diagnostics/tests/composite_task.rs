@@ -1,4 +1,4 @@
-use rosrust_diagnostics::{CompositeTask, FunctionExt, Level, Status, Task};
+use rosrust_diagnostics::{AggregateStrategy, CompositeTask, FunctionExt, Level, Status, Task};
 
 #[test]
 fn oks_get_ignored_when_issues_arise() {
@@ -89,3 +89,49 @@ fn oks_get_perserved_when_there_are_no_issues() {
     assert_eq!(target.values[2].key, "three");
     assert_eq!(target.values[2].value, "3");
 }
+
+fn isolated_children(strategy: AggregateStrategy) -> CompositeTask<'static> {
+    let mut task = CompositeTask::with_strategy("composite_task", strategy);
+    task.add(
+        (|status: &mut Status| {
+            status.set_summary(Level::Ok, "fine");
+            status.add("value", 1);
+        })
+        .into_task("ok"),
+    );
+    task.add(
+        (|status: &mut Status| {
+            status.set_summary(Level::Warn, "slow");
+            status.add("value", 2);
+        })
+        .into_task("warn"),
+    );
+    task.add(
+        (|status: &mut Status| status.set_summary(Level::Warn, "late")).into_task("also_warn"),
+    );
+    task
+}
+
+#[test]
+fn worst_strategy_lists_worst_children() {
+    let mut target = Status::default();
+    target.set_summary(Level::Ok, "start");
+    isolated_children(AggregateStrategy::Worst).run(&mut target);
+
+    assert_eq!(target.level, Level::Warn);
+    assert_eq!(&target.message, "warn: slow; also_warn: late");
+    assert_eq!(target.values.len(), 2);
+    assert_eq!(target.values[0].key, "ok: value");
+    assert_eq!(target.values[0].value, "1");
+    assert_eq!(target.values[1].key, "warn: value");
+    assert_eq!(target.values[1].value, "2");
+}
+
+#[test]
+fn all_strategy_lists_every_child() {
+    let mut target = Status::default();
+    isolated_children(AggregateStrategy::All).run(&mut target);
+
+    assert_eq!(target.level, Level::Warn);
+    assert_eq!(&target.message, "ok: fine; warn: slow; also_warn: late");
+}
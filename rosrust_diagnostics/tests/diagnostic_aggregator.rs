@@ -0,0 +1,61 @@
+use rosrust_diagnostics::{DiagnosticAggregator, FunctionExt, Level, Status, Task};
+
+mod util;
+
+#[test]
+fn reports_worst_level_and_failing_children() {
+    let front = (|status: &mut Status| {
+        status.set_summary(Level::Ok, "fine");
+        status.add("Rate", 10);
+    })
+    .into_task("Front lidar");
+    let rear = (|status: &mut Status| {
+        status.set_summary(Level::Warn, "slow");
+    })
+    .into_task("Rear lidar");
+    let imu = (|status: &mut Status| {
+        status.set_summary(Level::Error, "offline");
+    })
+    .into_task("IMU");
+
+    let aggregator = DiagnosticAggregator::new("Sensors")
+        .with_task(front)
+        .with_task(rear)
+        .with_task(imu);
+
+    let mut status = Status::default();
+    aggregator.run(&mut status);
+
+    assert_eq!(aggregator.name(), "Sensors");
+    assert_eq!(aggregator.len(), 3);
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Rear lidar: slow; IMU: offline");
+    assert_eq!(util::value_of(&status, "Front lidar: Rate"), Some("10"));
+}
+
+#[test]
+fn all_ok_children_are_ok() {
+    let mut aggregator = DiagnosticAggregator::new("Sensors");
+    aggregator
+        .add_task((|status: &mut Status| status.set_summary(Level::Ok, "fine")).into_task("A"));
+
+    let mut status = Status::default();
+    aggregator.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(status.message, "All sub-tasks are OK.");
+}
+
+#[test]
+fn aggregators_can_be_nested() {
+    let inner = DiagnosticAggregator::new("Lidars").with_task(
+        (|status: &mut Status| status.set_summary(Level::Warn, "slow")).into_task("Rear lidar"),
+    );
+    let outer = DiagnosticAggregator::new("Robot").with_task(inner);
+
+    let mut status = Status::default();
+    outer.run(&mut status);
+
+    assert_eq!(status.level, Level::Warn);
+    assert_eq!(status.message, "Lidars: Rear lidar: slow");
+}
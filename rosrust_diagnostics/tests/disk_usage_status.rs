@@ -0,0 +1,58 @@
+use rosrust_diagnostics::{DiskUsageStatus, Level, Status, Task};
+
+mod util;
+
+fn run(task: &DiskUsageStatus) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn reports_usage_of_file_system() {
+    let status = run(&DiskUsageStatus::new(
+        "Disk",
+        env!("CARGO_MANIFEST_DIR"),
+        101.0,
+        101.0,
+    ));
+
+    assert_eq!(status.level, Level::Ok);
+    let used = util::value_of(&status, "Used bytes")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let total = util::value_of(&status, "Total bytes")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let percent = util::value_of(&status, "Used percentage")
+        .unwrap()
+        .parse::<f64>()
+        .unwrap();
+    assert!(used <= total);
+    assert!((0.0..=100.0).contains(&percent));
+}
+
+#[test]
+fn thresholds_raise_level() {
+    let path = env!("CARGO_MANIFEST_DIR");
+
+    assert_eq!(
+        run(&DiskUsageStatus::new("Disk", path, 0.0, 101.0)).level,
+        Level::Warn,
+    );
+    assert_eq!(
+        run(&DiskUsageStatus::new("Disk", path, 0.0, 0.0)).level,
+        Level::Error,
+    );
+}
+
+#[test]
+fn missing_path_is_an_error() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("definitely_not_a_dir");
+    let status = run(&DiskUsageStatus::new("Disk", path, 90.0, 95.0));
+
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(util::value_of(&status, "Used bytes"), None);
+}
@@ -0,0 +1,60 @@
+use rosrust_diagnostics::{EnvVarStatus, Level, Status, Task};
+use std::env;
+
+mod util;
+
+// Every test uses its own variable, since tests run in parallel in the same process.
+
+#[test]
+fn set_variable_is_ok() {
+    env::set_var("ROSRUST_DIAGNOSTICS_TEST_SET", "anything");
+    let task = EnvVarStatus::new("Variable", "ROSRUST_DIAGNOSTICS_TEST_SET", None);
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(task.name(), "Variable");
+    assert_eq!(
+        util::value_of(&status, "Variable"),
+        Some("ROSRUST_DIAGNOSTICS_TEST_SET")
+    );
+    assert_eq!(util::value_of(&status, "Value"), Some("anything"));
+    assert_eq!(util::value_of(&status, "Expected value"), None);
+}
+
+#[test]
+fn expected_value_is_ok() {
+    env::set_var("ROSRUST_DIAGNOSTICS_TEST_EXPECTED", "11311");
+    let task = EnvVarStatus::new("Port", "ROSRUST_DIAGNOSTICS_TEST_EXPECTED", Some("11311"));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(util::value_of(&status, "Expected value"), Some("11311"));
+}
+
+#[test]
+fn missing_variable_is_a_warning() {
+    env::remove_var("ROSRUST_DIAGNOSTICS_TEST_MISSING");
+    let task = EnvVarStatus::new("Missing", "ROSRUST_DIAGNOSTICS_TEST_MISSING", Some("1"));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Warn);
+    assert_eq!(util::value_of(&status, "Value"), None);
+}
+
+#[test]
+fn unexpected_value_is_an_error() {
+    env::set_var("ROSRUST_DIAGNOSTICS_TEST_WRONG", "debug");
+    let task = EnvVarStatus::new("Mode", "ROSRUST_DIAGNOSTICS_TEST_WRONG", Some("release"));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(util::value_of(&status, "Value"), Some("debug"));
+}
@@ -0,0 +1,48 @@
+use rosrust_diagnostics::{FileExistsStatus, Level, Status, Task};
+use std::path::Path;
+
+mod util;
+
+#[test]
+fn existing_file_is_ok() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let task = FileExistsStatus::new("Manifest", &path);
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(task.name(), "Manifest");
+    assert_eq!(
+        util::value_of(&status, "Path"),
+        Some(&*path.display().to_string())
+    );
+    assert_eq!(util::value_of(&status, "Type"), Some("file"));
+}
+
+#[test]
+fn existing_directory_is_ok() {
+    let task = FileExistsStatus::new("Crate root", env!("CARGO_MANIFEST_DIR"));
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(util::value_of(&status, "Type"), Some("directory"));
+}
+
+#[test]
+fn missing_path_is_an_error() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("definitely_not_a_file.yaml");
+    let task = FileExistsStatus::new("Calibration", &path);
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(
+        util::value_of(&status, "Path"),
+        Some(&*path.display().to_string())
+    );
+    assert_eq!(util::value_of(&status, "Type"), None);
+}
@@ -29,8 +29,14 @@ fn frequency_status_test() {
     fs.run(&mut status2);
     rosrust::sleep(Duration::from_nanos(150_000_000));
     fs.tick();
+    let current0 = fs.current_frequency();
+    let current1 = fs.current_frequency();
     let mut status3 = Status::default();
     fs.run(&mut status3);
+    fs.reset_window();
+    let current_reset = fs.current_frequency();
+    let mut status_reset = Status::default();
+    fs.run(&mut status_reset);
     fs.clear();
     let mut status4 = Status::default();
     fs.run(&mut status4);
@@ -56,6 +62,21 @@ fn frequency_status_test() {
         "Min frequency exceeded but not reported"
     );
     assert_eq!(status4.level, Level::Error, "Freshly cleared should fail");
+    assert_eq!(
+        status_reset.level,
+        Level::Error,
+        "Freshly reset window should fail"
+    );
+    assert_eq!(
+        util::value_of(&status_reset, "Events since startup"),
+        Some("4"),
+        "Resetting the window should keep the events since startup"
+    );
+    assert_eq!(
+        util::value_of(&status4, "Events since startup"),
+        Some("0"),
+        "Clearing should reset the events since startup"
+    );
     assert_eq!(
         status0.name, "",
         "Name should not be set by FrequencyStatus"
@@ -65,4 +86,23 @@ fn frequency_status_test() {
         "Frequency Status",
         "Name should be \"Frequency Status\""
     );
+
+    let actual3: f64 = util::value_of(&status3, "Actual frequency (Hz)")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        (current0 - actual3).abs() < actual3 * 0.05,
+        "Current frequency {} should match reported frequency {}",
+        current0,
+        actual3
+    );
+    assert!(
+        (current1 - actual3).abs() < actual3 * 0.05,
+        "Reading the current frequency should not advance the window"
+    );
+    assert_eq!(
+        current_reset, 0.0,
+        "A freshly reset window should have a frequency of 0"
+    );
 }
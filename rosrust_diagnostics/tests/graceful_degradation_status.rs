@@ -0,0 +1,42 @@
+use rosrust_diagnostics::{GracefulDegradationStatus, Level, OperationalMode, Status, Task};
+
+mod util;
+
+#[test]
+fn reports_operational_mode() {
+    let task = GracefulDegradationStatus::new("Drive");
+
+    let mut normal = Status::default();
+    task.run(&mut normal);
+    assert_eq!(normal.level, Level::Ok);
+    assert_eq!(util::value_of(&normal, "Mode"), Some("Normal"));
+    assert_eq!(util::value_of(&normal, "Reason"), None);
+
+    task.set_degraded("Left camera offline");
+    assert_eq!(
+        task.mode(),
+        OperationalMode::Degraded("Left camera offline".into())
+    );
+    let mut degraded = Status::default();
+    task.run(&mut degraded);
+    assert_eq!(degraded.level, Level::Warn);
+    assert_eq!(util::value_of(&degraded, "Mode"), Some("Degraded"));
+    assert_eq!(
+        util::value_of(&degraded, "Reason"),
+        Some("Left camera offline")
+    );
+    assert!(degraded.message.contains("Left camera offline"));
+
+    task.set_emergency_stop("Bumper pressed");
+    let mut stopped = Status::default();
+    task.run(&mut stopped);
+    assert_eq!(stopped.level, Level::Error);
+    assert_eq!(util::value_of(&stopped, "Mode"), Some("Emergency stop"));
+    assert_eq!(util::value_of(&stopped, "Reason"), Some("Bumper pressed"));
+
+    task.set_normal();
+    let mut recovered = Status::default();
+    task.run(&mut recovered);
+    assert_eq!(recovered.level, Level::Ok);
+    assert_eq!(task.name(), "Drive");
+}
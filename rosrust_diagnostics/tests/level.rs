@@ -0,0 +1,13 @@
+use rosrust_diagnostics::Level;
+
+#[test]
+fn max_picks_more_severe_level() {
+    let levels = [Level::Ok, Level::Warn, Level::Error, Level::Stale];
+    for (i, &a) in levels.iter().enumerate() {
+        for (j, &b) in levels.iter().enumerate() {
+            let expected = levels[i.max(j)];
+            assert_eq!(a.max(b), expected);
+            assert_eq!(a | b, expected);
+        }
+    }
+}
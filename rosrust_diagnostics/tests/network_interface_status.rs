@@ -0,0 +1,36 @@
+use rosrust_diagnostics::{Level, NetworkInterfaceStatus, Status, Task};
+
+mod util;
+
+#[test]
+fn loopback_interface_is_ok() {
+    let task = NetworkInterfaceStatus::new("Loopback", "lo");
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(task.name(), "Loopback");
+    assert_eq!(util::value_of(&status, "Interface"), Some("lo"));
+    assert!(util::value_of(&status, "Operational state").is_some());
+    assert!(util::value_of(&status, "Total received bytes").is_some());
+    assert_eq!(util::value_of(&status, "Received bytes"), None);
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    let received = util::value_of(&status, "Received bytes").unwrap();
+    assert!(received.parse::<u64>().is_ok());
+}
+
+#[test]
+fn missing_interface_is_an_error() {
+    let task = NetworkInterfaceStatus::new("Missing", "definitely_not_a_nic0");
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Interface not found.");
+    assert_eq!(util::value_of(&status, "Operational state"), None);
+}
@@ -0,0 +1,41 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{DiagnosticArray, FunctionExt, Level, Status, TaskExt};
+use std::sync::mpsc;
+
+mod util;
+
+#[test]
+fn plugins_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PluginsTest);
+    rosrust::init("plugins_test");
+
+    assert!(
+        rosrust_diagnostics::force_update().is_err(),
+        "Updating before initialization should fail"
+    );
+
+    let early = (|status: &mut Status| status.set_summary(Level::Ok, "Early")).into_task("Early");
+    rosrust_diagnostics::register_task_plugin(early.boxed()).unwrap();
+    rosrust_diagnostics::init().unwrap();
+    let late = (|status: &mut Status| status.set_summary(Level::Warn, "Late")).into_task("Late");
+    rosrust_diagnostics::register_task_plugin(late.boxed()).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let subscriber = rosrust::subscribe("/diagnostics", 10, move |message: DiagnosticArray| {
+        tx.send(message).unwrap();
+    })
+    .unwrap();
+    while subscriber.publisher_count() == 0 {
+        rosrust::sleep(Duration::from_nanos(10_000_000));
+    }
+
+    rosrust_diagnostics::force_update().unwrap();
+
+    let message = rx.recv_timeout(std::time::Duration::from_secs(10)).unwrap();
+    let summaries = message
+        .status
+        .iter()
+        .map(|status| (status.name.as_str(), status.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(summaries, vec![("Early", "Early"), ("Late", "Late")]);
+}
@@ -0,0 +1,41 @@
+use rosrust_diagnostics::{Level, PublisherStatus, Status, Task};
+use rosrust_msg::std_msgs::Int32;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod util;
+
+fn run(task: &PublisherStatus<Int32>) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn publisher_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::PublisherStatusTest);
+    rosrust::init("publisher_status_test");
+
+    let publisher = Arc::new(rosrust::publish::<Int32>("/chatter", 10).unwrap());
+    let optional = PublisherStatus::new("Optional", Arc::clone(&publisher), 0);
+    let required = PublisherStatus::new("Required", Arc::clone(&publisher), 1);
+    let many = PublisherStatus::new("Many", Arc::clone(&publisher), 2);
+
+    let status = run(&optional);
+    assert_eq!(status.level, Level::Warn);
+    assert_eq!(util::value_of(&status, "Subscribers"), Some("0"));
+    assert_eq!(run(&required).level, Level::Error);
+    assert_eq!(run(&many).level, Level::Error);
+
+    let _subscriber = rosrust::subscribe("/chatter", 10, |_: Int32| {}).unwrap();
+    while publisher.subscriber_count() == 0 {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let status = run(&required);
+    assert_eq!(status.level, Level::Ok, "{}", status.message);
+    assert_eq!(util::value_of(&status, "Subscribers"), Some("1"));
+    assert_eq!(util::value_of(&status, "Minimum subscribers"), Some("1"));
+    assert_eq!(run(&optional).level, Level::Ok);
+    assert_eq!(run(&many).level, Level::Warn);
+}
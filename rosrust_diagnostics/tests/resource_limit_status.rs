@@ -0,0 +1,66 @@
+use rosrust_diagnostics::{Level, ResourceLimitStatus, ResourceLimits, Status, Task};
+
+mod util;
+
+fn run(task: &ResourceLimitStatus) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn usage_within_limits_is_ok() {
+    let task = ResourceLimitStatus::new(
+        "Resources",
+        ResourceLimits {
+            memory_warn_bytes: u64::MAX - 1,
+            memory_error_bytes: u64::MAX,
+            cpu_warn_percent: f64::INFINITY,
+            cpu_error_percent: f64::INFINITY,
+            threads_warn: u64::MAX - 1,
+            threads_error: u64::MAX,
+        },
+    );
+
+    let status = run(&task);
+    assert_eq!(status.level, Level::Ok, "{}", status.message);
+    assert_eq!(status.message, "Resource usage is normal.");
+    assert_eq!(task.name(), "Resources");
+    assert!(
+        util::value_of(&status, "Memory (bytes)")
+            .unwrap()
+            .parse::<u64>()
+            .unwrap()
+            > 0
+    );
+    assert!(
+        util::value_of(&status, "Threads")
+            .unwrap()
+            .parse::<u64>()
+            .unwrap()
+            >= 1
+    );
+    assert!(util::value_of(&status, "CPU usage (%)").is_some());
+}
+
+#[test]
+fn worst_resource_decides_level() {
+    let task = ResourceLimitStatus::new(
+        "Resources",
+        ResourceLimits {
+            memory_warn_bytes: 0,
+            memory_error_bytes: u64::MAX,
+            cpu_warn_percent: f64::INFINITY,
+            cpu_error_percent: f64::INFINITY,
+            threads_warn: 0,
+            threads_error: 0,
+        },
+    );
+
+    let status = run(&task);
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(
+        status.message,
+        "Memory usage is high.; Thread count is critical."
+    );
+}
@@ -0,0 +1,60 @@
+use rosrust_diagnostics::{Level, ServiceCallStatus, Status, Task};
+use rosrust_msg::std_srvs::{Trigger, TriggerReq, TriggerRes};
+use std::time::Duration;
+
+mod util;
+
+fn run(task: &ServiceCallStatus<Trigger>) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn service_call_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::ServiceCallStatusTest);
+    rosrust::init("service_call_status_test");
+
+    let _working = rosrust::service::<Trigger, _>("/working", |_| {
+        Ok(TriggerRes {
+            success: true,
+            message: "".into(),
+        })
+    })
+    .unwrap();
+    let _failing = rosrust::service::<Trigger, _>("/failing", |_| Err("Broken".into())).unwrap();
+    let _slow = rosrust::service::<Trigger, _>("/slow", |_| {
+        std::thread::sleep(Duration::from_secs(2));
+        Ok(TriggerRes::default())
+    })
+    .unwrap();
+
+    let timeout = Duration::from_millis(500);
+    let task =
+        ServiceCallStatus::<Trigger>::new("Working", "/working", TriggerReq {}, timeout).unwrap();
+    let status = run(&task);
+    assert_eq!(status.level, Level::Ok, "{}", status.message);
+    assert_eq!(task.name(), "Working");
+    assert_eq!(util::value_of(&status, "Service"), Some("/working"));
+    assert!(util::value_of(&status, "Call duration (ms)").is_some());
+    assert_eq!(run(&task).level, Level::Ok);
+
+    let task =
+        ServiceCallStatus::<Trigger>::new("Failing", "/failing", TriggerReq {}, timeout).unwrap();
+    let status = run(&task);
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Service responded with an error: Broken");
+
+    let task = ServiceCallStatus::<Trigger>::new("Slow", "/slow", TriggerReq {}, timeout).unwrap();
+    let start = std::time::Instant::now();
+    let status = run(&task);
+    assert!(start.elapsed() < Duration::from_millis(1500));
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Service call timed out.");
+
+    let task =
+        ServiceCallStatus::<Trigger>::new("Missing", "/missing", TriggerReq {}, timeout).unwrap();
+    let status = run(&task);
+    assert_eq!(status.level, Level::Error);
+    assert!(status.message.starts_with("Service unavailable"));
+}
@@ -0,0 +1,27 @@
+use rosrust_diagnostics::{Level, Status, SystemTimeStatus, Task};
+
+mod util;
+
+#[test]
+fn system_time_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::SystemTimeStatusTest);
+    rosrust::init("system_time_status_test");
+
+    // Without simulated time, ROS time is the wall clock.
+    let task = SystemTimeStatus::new("Clock", 100.0);
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(
+        status.level,
+        Level::Ok,
+        "Wall clock reported as out of sync"
+    );
+    assert_eq!(task.name(), "Clock");
+    let offset = util::value_of(&status, "Offset (ms)").unwrap();
+    assert!(offset.parse::<f64>().unwrap().abs() <= 100.0);
+    assert_eq!(
+        util::value_of(&status, "Maximum acceptable offset (ms)"),
+        Some("100")
+    );
+}
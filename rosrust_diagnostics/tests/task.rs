@@ -0,0 +1,54 @@
+use rosrust_diagnostics::{
+    FileExistsStatus, FrequencyStatus, FunctionExt, Heartbeat, Level, Status, Task, TaskExt,
+};
+use std::sync::Arc;
+
+// Compiling this function is enough to check that `Task` stays object safe.
+fn _task_is_object_safe(task: &dyn Task) -> &str {
+    task.name()
+}
+
+#[test]
+fn boxed_tasks_can_be_stored_together() {
+    let tasks: Vec<Box<dyn Task + Send + Sync>> = vec![
+        FileExistsStatus::new("Manifest", env!("CARGO_MANIFEST_DIR")).boxed(),
+        Heartbeat.boxed(),
+        (|status: &mut Status| status.set_summary(Level::Warn, "foo"))
+            .into_task("Function")
+            .boxed(),
+    ];
+
+    let names = tasks.iter().map(|task| task.name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["Manifest", "Heartbeat", "Function"]);
+
+    let mut status = Status::default();
+    tasks[2].run(&mut status);
+    assert_eq!(status.level, Level::Warn);
+    assert_eq!(status.message, "foo");
+}
+
+// `FrequencyStatus` needs a running node to be created, so this only checks that a shared one can
+// be handed to the `Updater` while other clones keep ticking it.
+fn _shared_frequency_status_is_a_task(frequency: &Arc<FrequencyStatus>) -> impl Task {
+    Arc::clone(frequency)
+}
+
+#[test]
+fn shared_tasks_delegate_to_inner_task() {
+    let function = Arc::new(
+        (|status: &mut Status| status.set_summary(Level::Error, "bar")).into_task("Function"),
+    );
+    let tasks: Vec<Box<dyn Task + Send + Sync>> =
+        vec![Arc::new(Heartbeat).boxed(), Arc::clone(&function).boxed()];
+
+    let names = tasks.iter().map(|task| task.name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["Heartbeat", "Function"]);
+
+    let mut status = Status::default();
+    tasks[1].run(&mut status);
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "bar");
+
+    let shared: Arc<dyn Task + Send + Sync> = function;
+    assert_eq!(shared.name(), "Function");
+}
@@ -0,0 +1,79 @@
+use rosrust_diagnostics::{Level, Status, Task, TcpConnectionStatus};
+use std::net::TcpListener;
+use std::time::Duration;
+
+mod util;
+
+fn run(task: &TcpConnectionStatus) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn reachable_endpoint_is_ok() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let task = TcpConnectionStatus::new(
+        "Endpoint",
+        address,
+        Duration::from_secs(1),
+        Duration::from_secs(0),
+    );
+
+    let status = run(&task);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(task.name(), "Endpoint");
+    assert_eq!(
+        util::value_of(&status, "Address"),
+        Some(&*address.to_string())
+    );
+    assert!(util::value_of(&status, "Connection latency").is_some());
+}
+
+#[test]
+fn unreachable_endpoint_is_an_error() {
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let task = TcpConnectionStatus::new(
+        "Endpoint",
+        address,
+        Duration::from_secs(1),
+        Duration::from_secs(0),
+    );
+
+    let status = run(&task);
+
+    assert_eq!(status.level, Level::Error);
+    assert!(status.message.starts_with("Connection failed"));
+    assert_eq!(util::value_of(&status, "Connection latency"), None);
+}
+
+#[test]
+fn results_are_reused_until_stale() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let cached = TcpConnectionStatus::new(
+        "Cached",
+        address,
+        Duration::from_secs(1),
+        Duration::from_secs(60),
+    );
+    let uncached = TcpConnectionStatus::new(
+        "Uncached",
+        address,
+        Duration::from_secs(1),
+        Duration::from_secs(0),
+    );
+
+    assert_eq!(run(&cached).level, Level::Ok);
+    assert_eq!(run(&uncached).level, Level::Ok);
+
+    drop(listener);
+
+    assert_eq!(run(&cached).level, Level::Ok);
+    assert_eq!(run(&uncached).level, Level::Error);
+}
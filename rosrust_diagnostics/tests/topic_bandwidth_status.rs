@@ -0,0 +1,48 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{Level, Status, Task, TopicBandwidthStatus};
+use rosrust_msg::std_msgs;
+
+mod util;
+
+fn run(task: &TopicBandwidthStatus<std_msgs::String>) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+#[test]
+fn topic_bandwidth_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::TopicBandwidthStatusTest);
+    rosrust::init("topic_bandwidth_status_test");
+
+    let task =
+        TopicBandwidthStatus::<std_msgs::String>::new("Chatter", "/chatter", 1000.0, 100_000.0)
+            .unwrap();
+    let publisher = rosrust::publish::<std_msgs::String>("/chatter", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        rosrust::sleep(Duration::from_nanos(10_000_000));
+    }
+
+    let status = run(&task);
+    assert_eq!(status.level, Level::Ok, "{}", status.message);
+    assert_eq!(util::value_of(&status, "Topic"), Some("/chatter"));
+    assert_eq!(util::value_of(&status, "Bandwidth (B/s)"), Some("0"));
+    assert!(util::value_of(&status, "Average message size (B)").is_none());
+
+    // Every message is a 4 byte length followed by 96 bytes of data.
+    let data = "x".repeat(96);
+    for _ in 0..50 {
+        publisher
+            .send(std_msgs::String { data: data.clone() })
+            .unwrap();
+        rosrust::sleep(Duration::from_nanos(2_000_000));
+    }
+    rosrust::sleep(Duration::from_nanos(100_000_000));
+
+    let status = run(&task);
+    assert_eq!(status.level, Level::Warn, "{}", status.message);
+    assert_eq!(
+        util::value_of(&status, "Average message size (B)"),
+        Some("100")
+    );
+}
@@ -0,0 +1,72 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{Level, Status, Task, TopicDiagnostic};
+use rosrust_msg::{geometry_msgs, std_msgs};
+
+mod util;
+
+fn run(task: &impl Task) -> Status {
+    let mut status = Status::default();
+    task.run(&mut status);
+    status
+}
+
+fn publish_for(publisher: &rosrust::Publisher<geometry_msgs::PoseStamped>, age: Duration) {
+    for _ in 0..10 {
+        let mut message = geometry_msgs::PoseStamped::default();
+        message.header.stamp = rosrust::now() - age;
+        publisher.send(message).unwrap();
+        rosrust::sleep(Duration::from_nanos(20_000_000));
+    }
+}
+
+#[test]
+fn topic_diagnostic_test() {
+    let _roscore = util::run_roscore_for(util::Feature::TopicDiagnosticTest);
+    rosrust::init("topic_diagnostic_test");
+
+    let task = TopicDiagnostic::<geometry_msgs::PoseStamped>::builder()
+        .topic("/poses")
+        .name("Poses")
+        .window_size(2)
+        .min_frequency(5.0)
+        .max_frequency(100.0)
+        .max_acceptable_duration(Duration::from_seconds(1))
+        .build()
+        .unwrap();
+    assert_eq!(task.name(), "Poses");
+    assert_eq!(task.topic(), "/poses");
+    let publisher = rosrust::publish::<geometry_msgs::PoseStamped>("/poses", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        rosrust::sleep(Duration::from_nanos(10_000_000));
+    }
+
+    // Neither messages, nor timestamps arrived yet.
+    run(&task);
+    let status = run(&task);
+    assert_eq!(status.level, Level::Error);
+
+    publish_for(&publisher, Duration::from_nanos(0));
+    let status = run(&task);
+    assert_eq!(status.level, Level::Ok, "{}", status.message);
+    assert!(status.values.iter().any(|v| v.key == "Topic"));
+
+    publish_for(&publisher, Duration::from_seconds(10));
+    let status = run(&task);
+    assert_eq!(status.level, Level::Error);
+    assert_eq!(status.message, "Timestamps too far in past seen.");
+
+    // Without a header, only the frequency is checked.
+    let task = TopicDiagnostic::<std_msgs::String>::builder()
+        .topic("/chatter")
+        .build()
+        .unwrap();
+    let publisher = rosrust::publish::<std_msgs::String>("/chatter", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        rosrust::sleep(Duration::from_nanos(10_000_000));
+    }
+    publisher.send(std_msgs::String::default()).unwrap();
+    rosrust::sleep(Duration::from_nanos(100_000_000));
+    let status = run(&task);
+    assert_eq!(status.level, Level::Warn);
+    assert!(status.message.contains("No data since last update."));
+}
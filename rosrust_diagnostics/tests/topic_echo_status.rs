@@ -0,0 +1,51 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{FrequencyStatus, Level, Status, Task, TopicEchoStatus};
+use rosrust_msg::std_msgs;
+
+mod util;
+
+#[test]
+fn topic_echo_status_test() {
+    let _roscore = util::run_roscore_for(util::Feature::TopicEchoStatusTest);
+    rosrust::init("topic_echo_status_test");
+
+    let frequency = FrequencyStatus::builder()
+        .window_size(2)
+        .min_frequency(5.0)
+        .max_frequency(50.0)
+        .build();
+    let task = TopicEchoStatus::<std_msgs::String>::new(
+        "Chatter",
+        "/chatter",
+        frequency,
+        Duration::from_nanos(200_000_000),
+    )
+    .unwrap();
+    let publisher = rosrust::publish::<std_msgs::String>("/chatter", 100).unwrap();
+    while publisher.subscriber_count() == 0 {
+        rosrust::sleep(Duration::from_nanos(10_000_000));
+    }
+
+    let mut status0 = Status::default();
+    task.run(&mut status0);
+
+    for _ in 0..10 {
+        publisher.send(std_msgs::String::default()).unwrap();
+        rosrust::sleep(Duration::from_nanos(20_000_000));
+    }
+    let mut status1 = Status::default();
+    task.run(&mut status1);
+
+    rosrust::sleep(Duration::from_nanos(300_000_000));
+    let mut status2 = Status::default();
+    task.run(&mut status2);
+
+    assert_eq!(status0.level, Level::Error, "No messages should fail");
+    assert_eq!(util::value_of(&status0, "Last message time (s)"), None);
+    assert_eq!(status1.level, Level::Ok, "Fresh messages at the right rate");
+    assert!(task.last_received().is_some());
+    assert!(util::value_of(&status1, "Actual frequency (Hz)").is_some());
+    assert_eq!(status2.level, Level::Error, "Stale messages not reported");
+    assert_eq!(util::value_of(&status2, "Topic"), Some("/chatter"));
+    assert_eq!(task.name(), "Chatter");
+}
@@ -0,0 +1,29 @@
+use rosrust_diagnostics::{FunctionExt, Level, Status, Updater};
+
+mod util;
+
+#[test]
+fn updater_snapshot_test() {
+    let _roscore = util::run_roscore_for(util::Feature::UpdaterSnapshotTest);
+    rosrust::init("updater_snapshot_test");
+
+    let ok = (|status: &mut Status| status.set_summary(Level::Ok, "All good")).into_task("Ok");
+    let silent = (|_: &mut Status| {}).into_task("Silent");
+
+    let mut updater = Updater::new().unwrap();
+    updater.set_hardware_id("snapshot_hardware");
+    updater.add_task(&ok).unwrap();
+    updater.add_task(&silent).unwrap();
+
+    let statuses = updater.snapshot();
+    assert_eq!(statuses.len(), 2);
+
+    assert_eq!(statuses[0].name, "Ok");
+    assert_eq!(statuses[0].hardware_id, "snapshot_hardware");
+    assert_eq!(statuses[0].level, Level::Ok);
+    assert_eq!(statuses[0].message, "All good");
+
+    assert_eq!(statuses[1].name, "Silent");
+    assert_eq!(statuses[1].level, Level::Error);
+    assert_eq!(statuses[1].message, "No message was set");
+}
@@ -1,4 +1,8 @@
+// Not every test uses every helper.
+#![allow(dead_code)]
+
 pub use child_process_terminator::ChildProcessTerminator;
+use rosrust_diagnostics::Status;
 use std::env;
 use std::process::Command;
 use std::thread::sleep;
@@ -34,11 +38,28 @@ pub fn run_roscore_for(feature: Feature) -> ChildProcessTerminator {
     run_roscore(generate_port(feature))
 }
 
-#[allow(dead_code)]
+pub fn value_of<'a>(status: &'a Status, key: &str) -> Option<&'a str> {
+    status
+        .values
+        .iter()
+        .find(|v| v.key == key)
+        .map(|v| v.value.as_str())
+}
+
 #[repr(u32)]
 pub enum Feature {
     TimestampStatusTest = 1,
     FrequencyStatusTest = 2,
+    TopicEchoStatusTest = 3,
+    SystemTimeStatusTest = 4,
+    UpdaterSnapshotTest = 5,
+    ServiceCallStatusTest = 6,
+    PublisherStatusTest = 7,
+    BandwidthStatusTest = 8,
+    TopicDiagnosticTest = 9,
+    WatchdogTest = 10,
+    TopicBandwidthStatusTest = 11,
+    PluginsTest = 12,
 }
 
 fn generate_port(feature: Feature) -> u32 {
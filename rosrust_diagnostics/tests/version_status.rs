@@ -0,0 +1,47 @@
+use rosrust_diagnostics::{Level, Status, Task, VersionStatus};
+
+mod util;
+
+fn run(version: &str, expected: Option<&str>) -> Status {
+    let mut status = Status::default();
+    VersionStatus::new("Driver", version, expected).run(&mut status);
+    status
+}
+
+#[test]
+fn version_without_expectation_is_ok() {
+    let task = VersionStatus::new("Driver", "not even semver", None);
+
+    let mut status = Status::default();
+    task.run(&mut status);
+
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(status.message, "not even semver");
+    assert_eq!(task.name(), "Driver");
+    assert_eq!(util::value_of(&status, "Version"), Some("not even semver"));
+    assert_eq!(util::value_of(&status, "Expected version"), None);
+}
+
+#[test]
+fn matching_version_is_ok() {
+    let status = run("1.2.3", Some("1.2.3"));
+    assert_eq!(status.level, Level::Ok);
+    assert_eq!(util::value_of(&status, "Expected version"), Some("1.2.3"));
+}
+
+#[test]
+fn minor_mismatch_is_a_warning() {
+    assert_eq!(run("1.3.0", Some("1.2.3")).level, Level::Warn);
+    assert_eq!(run("1.2.4", Some("1.2.3")).level, Level::Warn);
+}
+
+#[test]
+fn major_mismatch_is_an_error() {
+    assert_eq!(run("2.0.0", Some("1.2.3")).level, Level::Error);
+    assert_eq!(run("0.9.0", Some("1.2.3")).level, Level::Error);
+}
+
+#[test]
+fn invalid_version_is_an_error() {
+    assert_eq!(run("1.2", Some("1.2.3")).level, Level::Error);
+}
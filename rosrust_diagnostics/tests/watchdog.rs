@@ -0,0 +1,41 @@
+use rosrust::Duration;
+use rosrust_diagnostics::{Level, Status, Task, Watchdog};
+
+mod util;
+
+#[test]
+fn watchdog_test() {
+    let _roscore = util::run_roscore_for(util::Feature::WatchdogTest);
+    rosrust::init("watchdog_test");
+
+    let watchdog = Watchdog::builder()
+        .name("Control loop")
+        .timeout(Duration::from_nanos(100_000_000))
+        .error_message("Control loop stalled.")
+        .build();
+
+    let mut status0 = Status::default();
+    watchdog.run(&mut status0);
+    watchdog.tick();
+    let mut status1 = Status::default();
+    watchdog.run(&mut status1);
+    rosrust::sleep(Duration::from_nanos(200_000_000));
+    let mut status2 = Status::default();
+    watchdog.run(&mut status2);
+    watchdog.tick();
+    watchdog.clear();
+    let mut status3 = Status::default();
+    watchdog.run(&mut status3);
+
+    assert_eq!(status0.level, Level::Error, "No events but not reported");
+    assert_eq!(status0.message, "Control loop stalled.");
+    assert_eq!(status1.level, Level::Ok, "Fresh event but reported error");
+    assert_eq!(
+        status2.level,
+        Level::Error,
+        "Timeout exceeded but not reported"
+    );
+    assert_eq!(status2.message, "Control loop stalled.");
+    assert_eq!(status3.level, Level::Error, "Freshly cleared should fail");
+    assert_eq!(watchdog.name(), "Control loop");
+}